@@ -0,0 +1,253 @@
+// Turns the campaign in assets/levels.txt into a Rust table of Level records, the
+// cart has no room for a float parser so the text is only ever read here. Also
+// works out the fixed point sine table, so the cart never calls the runtime's sin
+
+use std::fmt::Write;
+
+const LEVELS: &str = "assets/levels.txt";
+const MAX_PADS: usize = 4;
+// Names of the Body variants, as written in the file
+const BODIES: [&str; 4] = ["moon", "mars", "io", "asteroid"];
+// Each body's pull compared to the Moon's, in the same order, as in body.rs
+const BODY_GRAVITY: [f64; 4] = [1.0, 1.5, 1.1, 0.3];
+// Keys with no sensible default, seed & wind can be left at 0
+const REQUIRED: [&str; 7] = [
+    "body", "gravity", "fuel", "terrain", "pad", "start", "limits",
+];
+// Width of the world in terrain units & furthest along a random pad is put, as in surface.rs
+const WORLD_WIDTH: f32 = 20.0;
+const RANDOM_PAD_MAX_X: f32 = 18.0;
+// Lowest the ship can start, the view zooms with height & has nothing to divide by at 0
+const MIN_START_Y: f64 = 10.0;
+// Steps in a quarter turn of the sine table
+const SIN_STEPS: usize = 256;
+
+#[derive(Clone, Default)]
+struct Level {
+    body: String,
+    seed: u32,
+    gravity: f64,
+    fuel: f64,
+    high: f32,
+    smooth: f32,
+    // x is None for a randomly placed pad
+    pads: Vec<(Option<f32>, f32, u32)>,
+    start: [f64; 4],
+    limits: [f64; 3],
+    wind: [f64; 2],
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", LEVELS);
+
+    let src = std::fs::read_to_string(LEVELS).unwrap_or_else(|e| panic!("{}: {}", LEVELS, e));
+    let levels = parse(&src).unwrap_or_else(|e| panic!("{} {}", LEVELS, e));
+
+    let mut out = String::new();
+    writeln!(out, "pub static LEVELS: [Level; {}] = [", levels.len()).unwrap();
+    for (i, l) in levels.iter().enumerate() {
+        let seed = match l.seed {
+            0 => String::from("None"),
+            s => format!("Some({})", s),
+        };
+        let mut pads = String::new();
+        for n in 0..MAX_PADS {
+            let (x, width, multiplier) = l.pads.get(n).copied().unwrap_or((None, 0.0, 0));
+            write!(
+                pads,
+                "Pad {{ x: {:?}, width: {:?}, multiplier: {} }}, ",
+                x, width, multiplier
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "    Level {{").unwrap();
+        writeln!(out, "        number: {},", i + 1).unwrap();
+        writeln!(out, "        seed: {},", seed).unwrap();
+        writeln!(out, "        body: Body::{},", body_variant(&l.body)).unwrap();
+        writeln!(out, "        gravity: {:?},", l.gravity).unwrap();
+        writeln!(out, "        fuel: {:?},", l.fuel).unwrap();
+        writeln!(out, "        pads: [{}],", pads).unwrap();
+        writeln!(out, "        pad_count: {},", l.pads.len()).unwrap();
+        writeln!(out, "        high: {:?},", l.high).unwrap();
+        writeln!(out, "        smooth: {:?},", l.smooth).unwrap();
+        writeln!(
+            out,
+            "        start_pos: Point {{ x: {:?}, y: {:?} }},",
+            l.start[0], l.start[1]
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        start_velocity: Point {{ x: {:?}, y: {:?} }},",
+            l.start[2], l.start[3]
+        )
+        .unwrap();
+        writeln!(out, "        max_speed: {:?},", l.limits[0]).unwrap();
+        writeln!(out, "        max_angle: {:?},", l.limits[1]).unwrap();
+        writeln!(out, "        max_drift: {:?},", l.limits[2]).unwrap();
+        writeln!(out, "        wind: {:?},", l.wind[0]).unwrap();
+        writeln!(out, "        gust: {:?},", l.wind[1]).unwrap();
+        writeln!(out, "    }},").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let dest = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("levels.rs");
+    std::fs::write(dest, out).unwrap();
+
+    write_sin_table();
+}
+
+// Quarter wave of sine in Q16.16, with both ends included so lookups can interpolate
+fn write_sin_table() {
+    let mut out = String::new();
+    writeln!(out, "const SIN_TAB: [i32; {}] = [", SIN_STEPS + 1).unwrap();
+    for i in 0..=SIN_STEPS {
+        let angle = i as f64 / SIN_STEPS as f64 * std::f64::consts::FRAC_PI_2;
+        writeln!(out, "    {},", (angle.sin() * 65536.0).round() as i32).unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let dest = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("sin_tab.rs");
+    std::fs::write(dest, out).unwrap();
+}
+
+// moon -> Moon
+fn body_variant(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+fn parse(src: &str) -> Result<Vec<Level>, String> {
+    let mut levels: Vec<Level> = Vec::new();
+    // Pads are replaced as a set, the first pad line in a level clears the inherited ones
+    let mut new_pads = false;
+    // Keys the first level has set
+    let mut first_keys: Vec<&str> = Vec::new();
+
+    for (line_no, line) in src.lines().enumerate() {
+        let err = |e: &str| format!("line {}: {}", line_no + 1, e);
+        let line = line.split('#').next().unwrap_or("");
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&key, values)) = words.split_first() else {
+            continue;
+        };
+
+        if key == "level" {
+            levels.push(levels.last().cloned().unwrap_or_default());
+            new_pads = false;
+            continue;
+        }
+
+        if levels.len() == 1 {
+            first_keys.push(key);
+        }
+        let level = levels.last_mut().ok_or(err("expected 'level' first"))?;
+        let expected = match key {
+            "body" | "seed" | "gravity" | "fuel" => 1,
+            "terrain" | "wind" => 2,
+            "pad" | "limits" => 3,
+            "start" => 4,
+            _ => return Err(err(&format!("unknown key '{}'", key))),
+        };
+        if values.len() != expected {
+            return Err(err(&format!("'{}' takes {} values", key, expected)));
+        }
+        let num = |i: usize| -> Result<f64, String> {
+            values[i]
+                .parse()
+                .map_err(|_| err(&format!("bad number '{}'", values[i])))
+        };
+
+        match key {
+            "body" => {
+                if !BODIES.contains(&values[0]) {
+                    return Err(err(&format!("unknown body '{}'", values[0])));
+                }
+                level.body = values[0].to_string();
+            }
+            "seed" => level.seed = num(0)? as u32,
+            "gravity" => level.gravity = num(0)?,
+            "fuel" => level.fuel = num(0)?,
+            "terrain" => {
+                level.high = num(0)? as f32;
+                level.smooth = num(1)? as f32;
+            }
+            "pad" => {
+                if !new_pads {
+                    level.pads.clear();
+                    new_pads = true;
+                }
+                if level.pads.len() == MAX_PADS {
+                    return Err(err("too many pads"));
+                }
+                let x = match values[0] {
+                    "?" => None,
+                    _ => Some(num(0)? as f32),
+                };
+                let width = num(1)? as f32;
+                if width <= 0.0 || x.is_some_and(|x| x < 0.0) {
+                    return Err(err("pad can't have a negative x or width"));
+                }
+                if x.unwrap_or(RANDOM_PAD_MAX_X) + width > WORLD_WIDTH {
+                    return Err(err(&format!(
+                        "pad runs past the world's width of {}",
+                        WORLD_WIDTH
+                    )));
+                }
+                level.pads.push((x, width, num(2)? as u32));
+            }
+            "start" => level.start = [num(0)?, num(1)?, num(2)?, num(3)?],
+            "limits" => level.limits = [num(0)?, num(1)?, num(2)?],
+            "wind" => level.wind = [num(0)?, num(1)?],
+            _ => unreachable!(),
+        }
+    }
+
+    if levels.is_empty() {
+        return Err(String::from("has no levels"));
+    }
+    if let Some(key) = REQUIRED.iter().find(|key| !first_keys.contains(key)) {
+        return Err(format!("first level must set '{}'", key));
+    }
+
+    // After inheritance, so a later level can't zero out something the game divides by
+    for (i, level) in levels.iter().enumerate() {
+        let err = |e: &str| format!("level {}: {}", i + 1, e);
+        if level.gravity <= 0.0 || level.fuel <= 0.0 || level.smooth <= 0.0 {
+            return Err(err("gravity, fuel & terrain smoothness must be above 0"));
+        }
+        if level.start[1] < MIN_START_Y {
+            return Err(err(&format!(
+                "ship must start at a Y of {} or more",
+                MIN_START_Y
+            )));
+        }
+        if level.limits.iter().any(|&limit| limit <= 0.0) {
+            return Err(err("landing limits must be above 0"));
+        }
+    }
+
+    // The campaign gets harder, so the pull the ship feels on each world has to grow
+    let felt = |level: &Level| {
+        let body = BODIES.iter().position(|&b| b == level.body).unwrap();
+        level.gravity * BODY_GRAVITY[body]
+    };
+    for (i, pair) in levels.windows(2).enumerate() {
+        if felt(&pair[1]) <= felt(&pair[0]) {
+            return Err(format!(
+                "level {}: gravity scaled for {} is {:.6}, it must be above level {}'s {:.6}",
+                i + 2,
+                pair[1].body,
+                felt(&pair[1]),
+                i + 1,
+                felt(&pair[0])
+            ));
+        }
+    }
+
+    Ok(levels)
+}
@@ -0,0 +1,11 @@
+// Headless simulation of the game, runs a seeded game for a number of frames
+// feeding gamepad state from an input script, then prints the final state
+
+// The cart itself is built for wasm32, the simulator only makes sense on a native host
+#[cfg(not(target_arch = "wasm32"))]
+mod sim;
+
+fn main() {
+    #[cfg(not(target_arch = "wasm32"))]
+    sim::run();
+}
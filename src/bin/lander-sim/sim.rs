@@ -0,0 +1,205 @@
+use cart::framebuffer::Framebuffer;
+use cart::game::Game;
+use cart::gfx;
+use cart::platform::Platform;
+use cart::replay::Replay;
+use std::process::exit;
+
+const USAGE: &str =
+    "usage: lander-sim [--seed N] [--level N] [--frames N] [--input FILE|-] [--title]
+                  [--png FILE] [--record FILE] [--replay FILE|-]
+
+--level starts the campaign from a later level, the default is 1. Levels with
+terrain of their own ignore --seed.
+--title starts on the title screen like the cart does, rather than in flight.
+--png saves the last frame drawn as an image.
+--record saves a replay of the run, as hex text.
+--replay plays back a saved replay, or one copied from the cart's debug console,
+instead of a seed & input script. It runs for the length of the replay by default.
+
+Input scripts hold one gamepad byte per frame, decimal or 0x hex, separated by
+whitespace. A value can be repeated with VALUE*COUNT, and # starts a comment.
+Frames past the end of the script have no buttons pressed.";
+
+struct Args {
+    seed: u32,
+    level: u32,
+    frames: Option<u32>,
+    input: Option<String>,
+    record: Option<String>,
+    replay: Option<String>,
+    png: Option<String>,
+    title: bool,
+}
+
+pub fn run() {
+    let args = parse_args().unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, USAGE);
+        exit(2);
+    });
+
+    let script = match &args.input {
+        Some(path) => read_input(path)
+            .and_then(|s| parse_script(&s))
+            .unwrap_or_else(fail),
+        None => Vec::new(),
+    };
+
+    let replay = args.replay.as_ref().map(|path| {
+        read_input(path)
+            .and_then(|s| Replay::from_hex(&s).ok_or(format!("{}: not a valid replay", path)))
+            .unwrap_or_else(fail)
+    });
+
+    let mut host = Framebuffer::new();
+    host.set_palette(gfx::PALETTE);
+    let mut game = Game::new();
+    let mut frames = args.frames.unwrap_or(600);
+    if let Some(replay) = replay {
+        frames = args.frames.unwrap_or(replay.len() as u32);
+        game.play_replay(replay);
+    } else if args.title {
+        game.new_game_seeded(0, args.seed);
+    } else {
+        game.play_level(args.seed, args.level);
+    }
+
+    for frame in 0..frames as usize {
+        host.clear();
+        host.gamepad = script.get(frame).copied().unwrap_or(0);
+        game.update(&mut host);
+    }
+
+    if let Some(path) = &args.png {
+        host.save_png(path)
+            .map_err(|e| format!("{}: {}", path, e))
+            .unwrap_or_else(fail);
+    }
+
+    if let Some(path) = &args.record {
+        std::fs::write(path, game.get_replay().to_hex() + "\n")
+            .map_err(|e| format!("{}: {}", path, e))
+            .unwrap_or_else(fail);
+    }
+
+    let ship = game.get_ship();
+    println!("frames: {}", frames);
+    println!("seed: {}", game.get_replay().seed);
+    println!("level: {}", game.get_level().number);
+    println!("lives: {}", game.get_lives());
+    println!("score: {}", game.get_score());
+    println!("fuel: {:.1}", ship.get_fuel());
+    println!("mass: {:.3}", ship.get_mass());
+    println!("throttle: {:.2}", ship.get_throttle());
+    println!("wind: {:.3}", game.get_wind());
+    println!("landed: {}", ship.is_landed());
+    println!("destroyed: {}", ship.is_destroyed());
+    println!(
+        "crash_reason: {}",
+        ship.contact.describe(game.get_level()).replace('\n', " ")
+    );
+    println!("contact: {:?}", ship.contact);
+    if let Some(hit) = ship.hit {
+        println!(
+            "hit: {:.3} {:.3} normal {:.3} {:.3}",
+            hit.point.x.to_f64(),
+            hit.point.y.to_f64(),
+            hit.normal.x.to_f64(),
+            hit.normal.y.to_f64()
+        );
+    }
+    if let Some(t) = ship.impact_time {
+        println!("impact_time: {:.3}", t.to_f64());
+    }
+    println!("pos: {:.3} {:.3}", ship.get_pos().x, ship.get_pos().y);
+    println!(
+        "velocity: {:.5} {:.5}",
+        ship.get_velocity().x,
+        ship.get_velocity().y
+    );
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        seed: 0,
+        level: 1,
+        frames: None,
+        input: None,
+        record: None,
+        replay: None,
+        png: None,
+        title: false,
+    };
+
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let mut value = || argv.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--seed" => args.seed = parse_num(&value()?)?,
+            "--level" => args.level = parse_num(&value()?)?,
+            "--frames" => args.frames = Some(parse_num(&value()?)?),
+            "--input" => args.input = Some(value()?),
+            "--png" => args.png = Some(value()?),
+            "--title" => args.title = true,
+            "--record" => args.record = Some(value()?),
+            "--replay" => args.replay = Some(value()?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    if args.replay.is_some() && (args.input.is_some() || args.title || args.level != 1) {
+        return Err(String::from(
+            "--replay can't be used with --input, --title or --level",
+        ));
+    }
+
+    Ok(args)
+}
+
+fn fail<T>(err: String) -> T {
+    eprintln!("error: {}", err);
+    exit(1);
+}
+
+fn read_input(path: &str) -> Result<String, String> {
+    if path == "-" {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| e.to_string())
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+    }
+}
+
+fn parse_num(s: &str) -> Result<u32, String> {
+    let n = match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    n.map_err(|_| format!("bad number '{}'", s))
+}
+
+fn parse_script(src: &str) -> Result<Vec<u8>, String> {
+    let mut frames = Vec::new();
+
+    for (line_no, line) in src.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        for token in line.split_whitespace() {
+            let (value, count) = match token.split_once('*') {
+                Some((v, c)) => (v, parse_num(c)),
+                None => (token, Ok(1)),
+            };
+            let err = |e: String| format!("line {}: {}", line_no + 1, e);
+            let value = parse_num(value).map_err(err)?;
+            let count = count.map_err(err)?;
+            if value > 0xff {
+                return Err(err(format!("gamepad value {} is not a byte", value)));
+            }
+            frames.extend(std::iter::repeat_n(value as u8, count as usize));
+        }
+    }
+
+    Ok(frames)
+}
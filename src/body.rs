@@ -0,0 +1,62 @@
+// The worlds a level can be set on. Levels give their gravity & terrain as if on the
+// Moon, the body scales those and adds its own air drag & colours
+
+use crate::gfx;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Body {
+    Moon,
+    Mars,
+    Io,
+    Asteroid,
+}
+
+impl Body {
+    pub fn name(self) -> &'static str {
+        match self {
+            Body::Moon => "Moon",
+            Body::Mars => "Mars",
+            Body::Io => "Io",
+            Body::Asteroid => "Asteroid",
+        }
+    }
+
+    /// Pull compared to the Moon, toned down from the real thing so the ship can still fly.
+    /// build.rs has a copy, to check each level pulls harder than the last
+    pub fn gravity(self) -> f64 {
+        match self {
+            Body::Moon => 1.0,
+            Body::Mars => 1.5,
+            Body::Io => 1.1,
+            Body::Asteroid => 0.3,
+        }
+    }
+
+    /// Share of the ship's velocity lost to the atmosphere each frame
+    pub fn drag(self) -> f64 {
+        match self {
+            Body::Mars => 0.006,
+            _ => 0.0,
+        }
+    }
+
+    /// How much higher & smoother than the Moon's the terrain is
+    pub fn terrain(self) -> (f32, f32) {
+        match self {
+            Body::Moon => (1.0, 1.0),
+            // Broad dunes, rough volcanic ground & a craggy little rock
+            Body::Mars => (0.9, 1.4),
+            Body::Io => (1.0, 0.8),
+            Body::Asteroid => (0.9, 0.85),
+        }
+    }
+
+    pub fn palette(self) -> [u32; 4] {
+        match self {
+            Body::Moon => gfx::PALETTE,
+            Body::Mars => [0x100400, 0x5c1e08, 0xb04a1c, 0xff9a5c],
+            Body::Io => [0x080800, 0x4a4000, 0x9c8c10, 0xfff060],
+            Body::Asteroid => [0x000000, 0x303848, 0x687890, 0xc8d8f0],
+        }
+    }
+}
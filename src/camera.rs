@@ -0,0 +1,134 @@
+// The view of the world, following the ship. It zooms in as the ship comes down and
+// leads it in the direction it's moving, so there's more to see of where it's heading.
+// The ground the ship collides with is worked out for the view, so this is all in fixed
+// point & updated with the physics, to keep replays exact
+
+use crate::fixed::{Fixed, FixedPoint};
+use crate::polygon::Point;
+
+// Screen position the view is worked out around, where the ship sits when it's still
+const ANCHOR_X: Fixed = Fixed::from_int(80);
+const ANCHOR_Y: Fixed = Fixed::from_int(50);
+// Near the ground the zoom steps in at these heights, rather than creeping. Higher up
+// it zooms out smoothly with height, reaching the first step as the ship gets there
+const ZOOM_TIERS: [(Fixed, Fixed); 3] = [
+    (Fixed::from_int(50), Fixed::ONE),
+    (Fixed::from_int(70), Fixed::from_f64(1.5)),
+    (Fixed::from_int(90), Fixed::from_int(2)),
+];
+// How far back above a step the ship has to climb before the view zooms out again
+const TIER_SLACK: Fixed = Fixed::from_int(4);
+// Frames of velocity the view looks ahead by, & how far off the anchor it lets the ship go
+const LOOK_AHEAD: Fixed = Fixed::from_int(60);
+const MAX_LEAD_X: Fixed = Fixed::from_int(40);
+const MAX_LEAD_Y: Fixed = Fixed::from_int(20);
+// Share of the way to where it's heading the view moves each frame
+const EASE: Fixed = Fixed::from_f64(0.05);
+
+#[derive(Copy, Clone, Debug)]
+pub struct Camera {
+    /// Screen pixels to a world pixel across, the terrain's heights are scaled by it too
+    pub zoom: Fixed,
+    /// Where the ship sits on screen
+    pub ship: FixedPoint,
+    /// Where the ship is in the world, as of the last update
+    pub follow: FixedPoint,
+    // Zoom steps the ship is down past
+    tier: usize,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        Self {
+            zoom: Fixed::ONE,
+            ship: FixedPoint::new(ANCHOR_X, ANCHOR_Y),
+            follow: FixedPoint::default(),
+            tier: 0,
+        }
+    }
+
+    /// Jump straight to the ship, for the start of a flight
+    pub fn reset(&mut self, pos: FixedPoint) {
+        self.tier = 0;
+        self.zoom = self.target_zoom(pos.y);
+        self.ship = FixedPoint::new(ANCHOR_X, ANCHOR_Y);
+        self.follow = pos;
+    }
+
+    /// Ease towards the zoom for the ship's height & the lead for its velocity
+    pub fn update(&mut self, pos: FixedPoint, velocity: FixedPoint) {
+        let zoom = self.target_zoom(pos.y);
+        self.zoom += (zoom - self.zoom) * EASE;
+
+        // The ship sits back from the way it's going, sideways speed on screen is zoomed
+        let lead_x = (velocity.x * self.zoom * LOOK_AHEAD).clamp(-MAX_LEAD_X, MAX_LEAD_X);
+        let lead_y = (velocity.y * LOOK_AHEAD).clamp(-MAX_LEAD_Y, MAX_LEAD_Y);
+        self.ship.x += (ANCHOR_X - lead_x - self.ship.x) * EASE;
+        self.ship.y += (ANCHOR_Y - lead_y - self.ship.y) * EASE;
+        self.follow = pos;
+    }
+
+    /// Keep up with the ship without zooming or leading it, so it stays put on screen
+    /// while the ground moves round it
+    pub fn hold(&mut self, pos: FixedPoint) {
+        self.follow = pos;
+    }
+
+    fn target_zoom(&mut self, y: Fixed) -> Fixed {
+        let mut tier = 0;
+        for (i, &(from, _)) in ZOOM_TIERS.iter().enumerate() {
+            // Steps already taken hold until the ship climbs a little way back up
+            let slack = if i < self.tier {
+                TIER_SLACK
+            } else {
+                Fixed::ZERO
+            };
+            if y >= from - slack {
+                tier = i + 1;
+            }
+        }
+        self.tier = tier;
+
+        match tier {
+            0 => y / ZOOM_TIERS[0].0 * ZOOM_TIERS[0].1,
+            _ => ZOOM_TIERS[tier - 1].1,
+        }
+    }
+
+    /// World position at the anchor with the ship at pos, the offsets the ground is
+    /// worked out around
+    pub fn view_at(&self, pos: FixedPoint) -> FixedPoint {
+        FixedPoint::new(
+            pos.x - (self.ship.x - ANCHOR_X) / self.zoom,
+            pos.y - (self.ship.y - ANCHOR_Y),
+        )
+    }
+
+    pub fn view(&self) -> FixedPoint {
+        self.view_at(self.follow)
+    }
+
+    /// Where a point in the world is on screen
+    pub fn to_screen(&self, world: Point) -> Point {
+        let view = self.view().to_point();
+        Point::new(
+            ANCHOR_X.to_f64() + (world.x - view.x) * self.zoom.to_f64(),
+            ANCHOR_Y.to_f64() + (world.y - view.y),
+        )
+    }
+
+    /// Where a point on screen is in the world, with the ship at pos
+    pub fn to_world(&self, screen: Point, pos: FixedPoint) -> Point {
+        let view = self.view_at(pos).to_point();
+        Point::new(
+            view.x + (screen.x - ANCHOR_X.to_f64()) / self.zoom.to_f64(),
+            view.y + (screen.y - ANCHOR_Y.to_f64()),
+        )
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
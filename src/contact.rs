@@ -0,0 +1,88 @@
+// How a flight ended, if it has, along with what was measured at the time
+
+use crate::gfx;
+use crate::level::{Level, Pad};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Contact {
+    None,
+    /// Touched down within the limits, waiting for both legs to be down before it counts
+    Settling {
+        pad: Pad,
+        score_quality: u32,
+    },
+    /// Safe landing, quality is the precision bonus from 0 to 100
+    Landed {
+        pad: Pad,
+        score_quality: u32,
+    },
+    Crashed {
+        kind: CrashKind,
+    },
+    /// Came down on a pad but broke one of the landing limits
+    BadLanding(BadLanding),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CrashKind {
+    Terrain,
+    Space,
+    /// Fell over while settling on the pad
+    Tipped,
+}
+
+/// Speed is in HUD units, angle in degrees off vertical & vx is the raw sideways velocity
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BadLanding {
+    TooFast { speed: f64 },
+    TooSteep { angle: f64 },
+    Sideways { vx: f64 },
+}
+
+impl Contact {
+    pub fn is_landed(&self) -> bool {
+        matches!(self, Contact::Landed { .. })
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        matches!(self, Contact::Crashed { .. } | Contact::BadLanding(_))
+    }
+
+    /// What went wrong, for the crash screens, with the level's limit where one was broken
+    pub fn describe(&self, level: &Level) -> String {
+        match *self {
+            Contact::None | Contact::Settling { .. } | Contact::Landed { .. } => String::new(),
+            Contact::Crashed {
+                kind: CrashKind::Terrain,
+            } => String::from("Crashed into\n") + level.body.name() + "'s surface",
+            Contact::Crashed {
+                kind: CrashKind::Space,
+            } => String::from("You zoomed off\ninto space!"),
+            Contact::Crashed {
+                kind: CrashKind::Tipped,
+            } => String::from("Tipped over on\nthe landing pad"),
+            Contact::BadLanding(BadLanding::TooFast { speed }) => {
+                String::from("Landed too fast!\nat ")
+                    + gfx::whole(speed).as_str()
+                    + " (max "
+                    + gfx::tenths(level.max_speed).as_str()
+                    + ")"
+            }
+            Contact::BadLanding(BadLanding::TooSteep { angle }) => {
+                String::from("Landed at too\nsteep an angle\n")
+                    + gfx::tenths(angle.abs()).as_str()
+                    + " deg (max "
+                    + gfx::tenths(level.max_angle).as_str()
+                    + ")"
+            }
+            // Drift is shown scaled up like speed on the HUD
+            Contact::BadLanding(BadLanding::Sideways { vx }) => {
+                String::from("Sheared landing\nlegs, drift ")
+                    + gfx::tenths(vx.abs() * 100.0).as_str()
+                    + "\n(max "
+                    + gfx::tenths(level.max_drift * 100.0).as_str()
+                    + ")"
+            }
+        }
+    }
+}
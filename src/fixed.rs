@@ -0,0 +1,278 @@
+// Q16.16 fixed point numbers for the physics, integer maths comes out the same to the
+// bit on every runtime, so replays play back exactly. Sine & cosine come from a
+// quarter wave table that build.rs works out, with straight lines between the steps
+
+use crate::polygon::Point;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+include!(concat!(env!("OUT_DIR"), "/sin_tab.rs"));
+
+const FRAC_BITS: u32 = 16;
+const FRAC_MASK: i64 = (1 << FRAC_BITS) - 1;
+// Table steps in a quarter turn, & sine table steps per radian in Q16.16
+const QUARTER: usize = 256;
+const STEPS_PER_RADIAN: i64 = 10680707;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << FRAC_BITS);
+    pub const PI: Fixed = Fixed(205887);
+    pub const FRAC_PI_2: Fixed = Fixed(102944);
+
+    /// Straight to & from the raw Q16.16 bits
+    pub const fn from_bits(bits: i32) -> Self {
+        Fixed(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    pub const fn from_int(n: i32) -> Self {
+        Fixed(n << FRAC_BITS)
+    }
+
+    /// Nearest fixed point value, only for constants & input, never mid simulation
+    pub const fn from_f64(v: f64) -> Self {
+        let scaled = v * (1 << FRAC_BITS) as f64;
+        Fixed(if scaled < 0.0 {
+            scaled - 0.5
+        } else {
+            scaled + 0.5
+        } as i32)
+    }
+
+    pub fn from_f32(v: f32) -> Self {
+        Fixed::from_f64(v as f64)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1 << FRAC_BITS) as f64
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Whole part, rounding down
+    pub fn floor(self) -> i32 {
+        self.0 >> FRAC_BITS
+    }
+
+    pub fn ceil(self) -> i32 {
+        (self.0 + FRAC_MASK as i32) >> FRAC_BITS
+    }
+
+    pub fn abs(self) -> Self {
+        Fixed(self.0.abs())
+    }
+
+    /// -1, 0 or 1 as a plain number
+    pub fn signum(self) -> i32 {
+        self.0.signum()
+    }
+
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed::ZERO;
+        }
+
+        // The root of a Q16.16 number shifted up another 16 bits comes out in Q16.16
+        let n = (self.0 as u64) << FRAC_BITS;
+        let mut root = 0u64;
+        let mut bit = 1u64 << 46;
+        let mut rem = n;
+        while bit > rem {
+            bit >>= 2;
+        }
+        while bit != 0 {
+            if rem >= root + bit {
+                rem -= root + bit;
+                root = (root >> 1) + bit;
+            } else {
+                root >>= 1;
+            }
+            bit >>= 2;
+        }
+
+        Fixed(root as i32)
+    }
+
+    pub fn sin(self) -> Self {
+        let steps = (self.0 as i64 * STEPS_PER_RADIAN) >> FRAC_BITS;
+        let frac = steps & FRAC_MASK;
+        let step = ((steps >> FRAC_BITS) & (QUARTER as i64 * 4 - 1)) as usize;
+        let i = step % QUARTER;
+
+        // Mirror & flip the quarter wave for the rest of the turn
+        let (a, b) = match step / QUARTER {
+            0 => (SIN_TAB[i], SIN_TAB[i + 1]),
+            1 => (SIN_TAB[QUARTER - i], SIN_TAB[QUARTER - i - 1]),
+            2 => (-SIN_TAB[i], -SIN_TAB[i + 1]),
+            _ => (-SIN_TAB[QUARTER - i], -SIN_TAB[QUARTER - i - 1]),
+        };
+
+        Fixed(a + (((b - a) as i64 * frac) >> FRAC_BITS) as i32)
+    }
+
+    pub fn cos(self) -> Self {
+        (self + Fixed::FRAC_PI_2).sin()
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, rhs: Fixed) -> Fixed {
+        Fixed(((self.0 as i64 * rhs.0 as i64) >> FRAC_BITS) as i32)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    fn div(self, rhs: Fixed) -> Fixed {
+        Fixed((((self.0 as i64) << FRAC_BITS) / rhs.0 as i64) as i32)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Fixed) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Fixed) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Fixed) {
+        *self = *self * rhs;
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FixedPoint {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedPoint {
+    pub fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    pub fn from_point(p: Point) -> Self {
+        Self::new(Fixed::from_f64(p.x), Fixed::from_f64(p.y))
+    }
+
+    /// Back to floats for drawing & display
+    pub fn to_point(self) -> Point {
+        Point::new(self.x.to_f64(), self.y.to_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn close(a: Fixed, b: f64) -> bool {
+        (a.to_f64() - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn sin_matches_known_values() {
+        let cases = [
+            (0.0, 0.0),
+            (PI / 6.0, 0.5),
+            (PI / 2.0, 1.0),
+            (PI * 5.0 / 6.0, 0.5),
+            (PI, 0.0),
+            (PI * 3.0 / 2.0, -1.0),
+            (-PI / 6.0, -0.5),
+            (-PI / 2.0, -1.0),
+            (-PI * 5.0 / 4.0, (PI / 4.0).sin()),
+        ];
+        for (angle, want) in cases {
+            let got = Fixed::from_f64(angle).sin();
+            assert!(close(got, want), "sin({}) = {:?}", angle, got.to_f64());
+        }
+    }
+
+    #[test]
+    fn sin_wraps_whole_turns() {
+        for turns in [-3.0, -1.0, 1.0, 4.0] {
+            for angle in [0.3, 1.2, 2.9, -2.0] {
+                let got = Fixed::from_f64(angle + turns * 2.0 * PI).sin();
+                assert!(
+                    close(got, f64::sin(angle)),
+                    "sin({} + {} turns)",
+                    angle,
+                    turns
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cos_is_sin_a_quarter_on() {
+        assert!(close(Fixed::ZERO.cos(), 1.0));
+        assert!(close(Fixed::PI.cos(), -1.0));
+        assert!(close(Fixed::from_f64(-PI / 3.0).cos(), 0.5));
+    }
+
+    #[test]
+    fn sqrt_matches_known_values() {
+        assert_eq!(Fixed::from_int(4).sqrt(), Fixed::from_int(2));
+        assert_eq!(Fixed::from_f64(0.25).sqrt(), Fixed::from_f64(0.5));
+        assert_eq!(Fixed::from_int(10000).sqrt(), Fixed::from_int(100));
+        assert!(close(Fixed::from_int(2).sqrt(), std::f64::consts::SQRT_2));
+        assert_eq!(Fixed::ZERO.sqrt(), Fixed::ZERO);
+        assert_eq!(Fixed::from_int(-1).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn div_rounds_towards_zero() {
+        assert_eq!((Fixed::ONE / Fixed::from_int(3)).to_bits(), 21845);
+        assert_eq!((-Fixed::ONE / Fixed::from_int(3)).to_bits(), -21845);
+        assert_eq!(
+            Fixed::from_int(-7) / Fixed::from_int(2),
+            Fixed::from_f64(-3.5)
+        );
+        assert_eq!(
+            Fixed::from_int(10) / Fixed::from_f64(0.5),
+            Fixed::from_int(20)
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::headless::DISK_SIZE;
+use crate::platform::{Platform, SCREEN_SIZE};
+use crate::png;
+
+// 160x160 pixels packed 4 to a byte, the same layout as the WASM-4 framebuffer
+const BUFFER_SZ: usize = (SCREEN_SIZE * SCREEN_SIZE / 4) as usize;
+
+/// Software renderer following the WASM-4 drawing rules, so frames can be captured natively
+pub struct Framebuffer {
+    pub gamepad: u8,
+    pub disk: Vec<u8>,
+    palette: [u32; 4],
+    draw_colors: u16,
+    pixels: [u8; BUFFER_SZ],
+}
+
+impl Framebuffer {
+    pub fn new() -> Self {
+        Self {
+            gamepad: 0,
+            disk: Vec::new(),
+            // The WASM-4 power-on palette & draw colors
+            palette: [0xe0f8cf, 0x86c06c, 0x306850, 0x071821],
+            draw_colors: 0x1203,
+            pixels: [0; BUFFER_SZ],
+        }
+    }
+
+    /// Fill the screen with palette colour 0, like the console does before every update
+    pub fn clear(&mut self) {
+        self.pixels = [0; BUFFER_SZ];
+    }
+
+    /// Palette index (0-3) of a pixel
+    pub fn get_pixel(&self, x: i32, y: i32) -> u8 {
+        let idx = (y * SCREEN_SIZE + x) as usize;
+        (self.pixels[idx >> 2] >> ((idx & 3) << 1)) & 0x3
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut rows = Vec::new();
+        for y in 0..SCREEN_SIZE {
+            let mut row = vec![0u8; SCREEN_SIZE as usize / 4];
+            for x in 0..SCREEN_SIZE {
+                row[x as usize >> 2] |= self.get_pixel(x, y) << (6 - ((x & 3) << 1));
+            }
+            rows.push(row);
+        }
+        png::encode_2bpp(SCREEN_SIZE as u32, SCREEN_SIZE as u32, &self.palette, &rows)
+    }
+
+    pub fn save_png(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_png())
+    }
+
+    // Draw color n (1-4) from DRAW_COLORS, as a palette index, or None when transparent
+    fn draw_color(&self, n: u16) -> Option<u8> {
+        let dc = (self.draw_colors >> ((n - 1) * 4)) & 0xf;
+        if dc == 0 {
+            return None;
+        }
+        Some(((dc - 1) & 0x3) as u8)
+    }
+
+    fn point(&mut self, color: u8, x: i32, y: i32) {
+        if x < 0 || y < 0 || x >= SCREEN_SIZE || y >= SCREEN_SIZE {
+            return;
+        }
+        let idx = (y * SCREEN_SIZE + x) as usize;
+        let shift = (idx & 3) << 1;
+        let byte = &mut self.pixels[idx >> 2];
+        *byte = (*byte & !(0x3 << shift)) | (color << shift);
+    }
+
+    fn span(&mut self, color: u8, x1: i32, x2: i32, y: i32) {
+        for x in x1.max(0)..x2.min(SCREEN_SIZE) {
+            self.point(color, x, y);
+        }
+    }
+}
+
+impl Default for Framebuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Platform for Framebuffer {
+    fn set_palette(&mut self, palette: [u32; 4]) {
+        self.palette = palette;
+    }
+
+    fn set_draw_color(&mut self, idx: u16) {
+        self.draw_colors = idx;
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        let Some(color) = self.draw_color(1) else {
+            return;
+        };
+
+        // Always step downwards, same as the console so lines are pixel identical
+        let (mut x1, mut y1, x2, y2) = if y1 > y2 {
+            (x2, y2, x1, y1)
+        } else {
+            (x1, y1, x2, y2)
+        };
+        let dx = (x2 - x1).abs();
+        let sx = if x1 < x2 { 1 } else { -1 };
+        let dy = y2 - y1;
+        let mut err = if dx > dy { dx } else { -dy } / 2;
+
+        loop {
+            self.point(color, x1, y1);
+            if x1 == x2 && y1 == y2 {
+                break;
+            }
+            let e2 = err;
+            if e2 > -dx {
+                err -= dy;
+                x1 += sx;
+            }
+            if e2 < dy {
+                err += dx;
+                y1 += 1;
+            }
+        }
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        let end_x = x + width as i32;
+        let end_y = y + height as i32;
+
+        if let Some(fill) = self.draw_color(1) {
+            for yy in y.max(0)..end_y.min(SCREEN_SIZE) {
+                self.span(fill, x, end_x, yy);
+            }
+        }
+
+        if let Some(stroke) = self.draw_color(2) {
+            for yy in y.max(0)..end_y.min(SCREEN_SIZE) {
+                self.point(stroke, x, yy);
+                self.point(stroke, end_x - 1, yy);
+            }
+            self.span(stroke, x, end_x, y);
+            self.span(stroke, x, end_x, end_y - 1);
+        }
+    }
+
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        let fill = self.draw_color(1);
+        let stroke = self.draw_color(2);
+
+        // The console's midpoint ellipse, working in from the left & right edges while
+        // the top & bottom halves work out from the middle row
+        let a = width as i32 - 1;
+        let b = height as i32 - 1;
+        let b1 = b % 2;
+        let mut north = y + height as i32 / 2;
+        let mut west = x;
+        let mut east = x + width as i32 - 1;
+        // Even heights start the bottom half on the row above the top half
+        let mut south = north - b1;
+
+        let mut dx = 4 * (1 - a) * b * b;
+        let mut dy = 4 * (b1 + 1) * a * a;
+        let mut err = dx + dy + b1 * a * a;
+        let (step_y, step_x) = (8 * a * a, 8 * b * b);
+
+        loop {
+            if let Some(color) = stroke {
+                self.point(color, east, north);
+                self.point(color, west, north);
+                self.point(color, west, south);
+                self.point(color, east, south);
+            }
+            if let Some(color) = fill {
+                self.span(color, west + 1, east, north);
+                self.span(color, west + 1, east, south);
+            }
+
+            let err2 = 2 * err;
+            if err2 <= dy {
+                north += 1;
+                south -= 1;
+                dy += step_y;
+                err += dy;
+            }
+            if err2 >= dx || err2 > dy {
+                west += 1;
+                east -= 1;
+                dx += step_x;
+                err += dx;
+            }
+            if west > east {
+                break;
+            }
+        }
+
+        // Flat ovals finish their ends before the sides meet
+        while north - south < height as i32 {
+            if let Some(color) = stroke {
+                self.point(color, west - 1, north);
+                self.point(color, east + 1, north);
+                self.point(color, west - 1, south);
+                self.point(color, east + 1, south);
+            }
+            north += 1;
+            south -= 1;
+        }
+    }
+
+    fn hline(&mut self, x: i32, y: i32, len: u32) {
+        if let Some(color) = self.draw_color(1) {
+            self.span(color, x, x + len as i32, y);
+        }
+    }
+
+    fn vline(&mut self, x: i32, y: i32, len: u32) {
+        if let Some(color) = self.draw_color(1) {
+            for yy in y..y + len as i32 {
+                self.point(color, x, yy);
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32) {
+        let fg = self.draw_color(1);
+        let bg = self.draw_color(2);
+        let (mut cx, mut cy) = (x, y);
+
+        for c in text.chars() {
+            if c == '\n' {
+                cx = x;
+                cy += 8;
+                continue;
+            }
+
+            // Anything the font has no glyph for still takes up a character's space
+            if let Some(glyph) = (c as usize).checked_sub(32).and_then(|i| FONT.get(i)) {
+                for (row, bits) in glyph.iter().enumerate() {
+                    for col in 0..8 {
+                        // The glyphs are drawn with the clear bits, like a 1BPP blit
+                        let clear = bits & (0x80 >> col) == 0;
+                        if let Some(color) = if clear { fg } else { bg } {
+                            self.point(color, cx + col, cy + row as i32);
+                        }
+                    }
+                }
+            }
+            cx += 8;
+        }
+    }
+
+    fn tone(&mut self, _frequency: u32, _duration: u32, _volume: u32, _flags: u32) {}
+
+    fn gamepad(&self) -> u8 {
+        self.gamepad
+    }
+
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32 {
+        let n = dest.len().min(self.disk.len());
+        dest[..n].copy_from_slice(&self.disk[..n]);
+        n as u32
+    }
+
+    fn disk_write(&mut self, src: &[u8]) -> u32 {
+        let n = src.len().min(DISK_SIZE);
+        self.disk = src[..n].to_vec();
+        n as u32
+    }
+
+    fn trace(&mut self, _text: &str) {}
+}
+
+// The console's built in font from space to DEL, a glyph of 8 rows a character with the
+// leftmost pixel in the top bit. The console has more past DEL, the game never uses them
+const FONT: [[u8; 8]; 96] = [
+    [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], // space
+    [0xc7, 0xc7, 0xc7, 0xcf, 0xcf, 0xff, 0xcf, 0xff], // !
+    [0x93, 0x93, 0x93, 0xff, 0xff, 0xff, 0xff, 0xff], // "
+    [0x93, 0x01, 0x93, 0x93, 0x93, 0x01, 0x93, 0xff], // #
+    [0xef, 0x83, 0x2f, 0x83, 0xe9, 0x03, 0xef, 0xff], // $
+    [0x9d, 0x5b, 0x37, 0xef, 0xd9, 0xb5, 0x73, 0xff], // %
+    [0x8f, 0x27, 0x27, 0x8f, 0x25, 0x33, 0x81, 0xff], // &
+    [0xcf, 0xcf, 0xcf, 0xff, 0xff, 0xff, 0xff, 0xff], // '
+    [0xf3, 0xe7, 0xcf, 0xcf, 0xcf, 0xe7, 0xf3, 0xff], // (
+    [0x9f, 0xcf, 0xe7, 0xe7, 0xe7, 0xcf, 0x9f, 0xff], // )
+    [0xff, 0x93, 0xc7, 0x01, 0xc7, 0x93, 0xff, 0xff], // *
+    [0xff, 0xe7, 0xe7, 0x81, 0xe7, 0xe7, 0xff, 0xff], // +
+    [0xff, 0xff, 0xff, 0xff, 0xff, 0xcf, 0xcf, 0x9f], // ,
+    [0xff, 0xff, 0xff, 0x81, 0xff, 0xff, 0xff, 0xff], // -
+    [0xff, 0xff, 0xff, 0xff, 0xff, 0xcf, 0xcf, 0xff], // .
+    [0xfd, 0xfb, 0xf7, 0xef, 0xdf, 0xbf, 0x7f, 0xff], // /
+    [0xc7, 0xb3, 0x39, 0x39, 0x39, 0x9b, 0xc7, 0xff], // 0
+    [0xe7, 0xc7, 0xe7, 0xe7, 0xe7, 0xe7, 0x81, 0xff], // 1
+    [0x83, 0x39, 0xf1, 0xc3, 0x87, 0x1f, 0x01, 0xff], // 2
+    [0x81, 0xf3, 0xe7, 0xc3, 0xf9, 0x39, 0x83, 0xff], // 3
+    [0xe3, 0xc3, 0x93, 0x33, 0x01, 0xf3, 0xf3, 0xff], // 4
+    [0x03, 0x3f, 0x03, 0xf9, 0xf9, 0x39, 0x83, 0xff], // 5
+    [0xc3, 0x9f, 0x3f, 0x03, 0x39, 0x39, 0x83, 0xff], // 6
+    [0x01, 0x39, 0xf3, 0xe7, 0xcf, 0xcf, 0xcf, 0xff], // 7
+    [0x87, 0x3b, 0x1b, 0x87, 0x61, 0x79, 0x83, 0xff], // 8
+    [0x83, 0x39, 0x39, 0x81, 0xf9, 0xf3, 0x87, 0xff], // 9
+    [0xff, 0xcf, 0xcf, 0xff, 0xcf, 0xcf, 0xff, 0xff], // :
+    [0xff, 0xcf, 0xcf, 0xff, 0xcf, 0xcf, 0x9f, 0xff], // ;
+    [0xf3, 0xe7, 0xcf, 0x9f, 0xcf, 0xe7, 0xf3, 0xff], // <
+    [0xff, 0xff, 0x01, 0xff, 0x01, 0xff, 0xff, 0xff], // =
+    [0x9f, 0xcf, 0xe7, 0xf3, 0xe7, 0xcf, 0x9f, 0xff], // >
+    [0x83, 0x01, 0x39, 0xf3, 0xc7, 0xff, 0xc7, 0xff], // ?
+    [0x83, 0x7d, 0x45, 0x55, 0x41, 0x7f, 0x83, 0xff], // @
+    [0xc7, 0x93, 0x39, 0x39, 0x01, 0x39, 0x39, 0xff], // A
+    [0x03, 0x39, 0x39, 0x03, 0x39, 0x39, 0x03, 0xff], // B
+    [0xc3, 0x99, 0x3f, 0x3f, 0x3f, 0x99, 0xc3, 0xff], // C
+    [0x07, 0x33, 0x39, 0x39, 0x39, 0x33, 0x07, 0xff], // D
+    [0x01, 0x3f, 0x3f, 0x03, 0x3f, 0x3f, 0x01, 0xff], // E
+    [0x01, 0x3f, 0x3f, 0x03, 0x3f, 0x3f, 0x3f, 0xff], // F
+    [0xc1, 0x9f, 0x3f, 0x31, 0x39, 0x99, 0xc1, 0xff], // G
+    [0x39, 0x39, 0x39, 0x01, 0x39, 0x39, 0x39, 0xff], // H
+    [0x81, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0x81, 0xff], // I
+    [0xf9, 0xf9, 0xf9, 0xf9, 0xf9, 0x39, 0x83, 0xff], // J
+    [0x39, 0x33, 0x27, 0x0f, 0x07, 0x23, 0x31, 0xff], // K
+    [0x9f, 0x9f, 0x9f, 0x9f, 0x9f, 0x9f, 0x81, 0xff], // L
+    [0x39, 0x11, 0x01, 0x01, 0x29, 0x39, 0x39, 0xff], // M
+    [0x39, 0x19, 0x09, 0x01, 0x21, 0x31, 0x39, 0xff], // N
+    [0x83, 0x39, 0x39, 0x39, 0x39, 0x39, 0x83, 0xff], // O
+    [0x03, 0x39, 0x39, 0x39, 0x03, 0x3f, 0x3f, 0xff], // P
+    [0x83, 0x39, 0x39, 0x39, 0x21, 0x33, 0x85, 0xff], // Q
+    [0x03, 0x39, 0x39, 0x31, 0x07, 0x23, 0x31, 0xff], // R
+    [0x87, 0x33, 0x3f, 0x83, 0xf9, 0x39, 0x83, 0xff], // S
+    [0x81, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0xff], // T
+    [0x39, 0x39, 0x39, 0x39, 0x39, 0x39, 0x83, 0xff], // U
+    [0x39, 0x39, 0x39, 0x11, 0x83, 0xc7, 0xef, 0xff], // V
+    [0x39, 0x39, 0x29, 0x01, 0x01, 0x11, 0x39, 0xff], // W
+    [0x39, 0x11, 0x83, 0xc7, 0x83, 0x11, 0x39, 0xff], // X
+    [0x99, 0x99, 0x99, 0xc3, 0xe7, 0xe7, 0xe7, 0xff], // Y
+    [0x01, 0xf1, 0xe3, 0xc7, 0x8f, 0x1f, 0x01, 0xff], // Z
+    [0xc3, 0xcf, 0xcf, 0xcf, 0xcf, 0xcf, 0xc3, 0xff], // [
+    [0x7f, 0xbf, 0xdf, 0xef, 0xf7, 0xfb, 0xfd, 0xff], // \
+    [0x87, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0x87, 0xff], // ]
+    [0xc7, 0x93, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], // ^
+    [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0xff], // _
+    [0xef, 0xf7, 0xfb, 0xff, 0xff, 0xff, 0xff, 0xff], // `
+    [0xff, 0xff, 0x83, 0xf9, 0x81, 0x39, 0x81, 0xff], // a
+    [0x3f, 0x3f, 0x03, 0x39, 0x39, 0x39, 0x83, 0xff], // b
+    [0xff, 0xff, 0x81, 0x3f, 0x3f, 0x3f, 0x81, 0xff], // c
+    [0xf9, 0xf9, 0x81, 0x39, 0x39, 0x39, 0x81, 0xff], // d
+    [0xff, 0xff, 0x83, 0x39, 0x01, 0x3f, 0x83, 0xff], // e
+    [0xf1, 0xe7, 0x81, 0xe7, 0xe7, 0xe7, 0xe7, 0xff], // f
+    [0xff, 0xff, 0x81, 0x39, 0x39, 0x81, 0xf9, 0x83], // g
+    [0x3f, 0x3f, 0x03, 0x39, 0x39, 0x39, 0x39, 0xff], // h
+    [0xe7, 0xff, 0xc7, 0xe7, 0xe7, 0xe7, 0x81, 0xff], // i
+    [0xf3, 0xff, 0xe3, 0xf3, 0xf3, 0xf3, 0xf3, 0x87], // j
+    [0x3f, 0x3f, 0x31, 0x03, 0x07, 0x23, 0x31, 0xff], // k
+    [0xc7, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0x81, 0xff], // l
+    [0xff, 0xff, 0x03, 0x49, 0x49, 0x49, 0x49, 0xff], // m
+    [0xff, 0xff, 0x03, 0x39, 0x39, 0x39, 0x39, 0xff], // n
+    [0xff, 0xff, 0x83, 0x39, 0x39, 0x39, 0x83, 0xff], // o
+    [0xff, 0xff, 0x03, 0x39, 0x39, 0x03, 0x3f, 0x3f], // p
+    [0xff, 0xff, 0x81, 0x39, 0x39, 0x81, 0xf9, 0xf9], // q
+    [0xff, 0xff, 0x91, 0x8f, 0x9f, 0x9f, 0x9f, 0xff], // r
+    [0xff, 0xff, 0x83, 0x3f, 0x83, 0xf9, 0x03, 0xff], // s
+    [0xe7, 0xe7, 0x81, 0xe7, 0xe7, 0xe7, 0xe7, 0xff], // t
+    [0xff, 0xff, 0x39, 0x39, 0x39, 0x39, 0x81, 0xff], // u
+    [0xff, 0xff, 0x99, 0x99, 0x99, 0xc3, 0xe7, 0xff], // v
+    [0xff, 0xff, 0x49, 0x49, 0x49, 0x49, 0x81, 0xff], // w
+    [0xff, 0xff, 0x39, 0x01, 0xc7, 0x01, 0x39, 0xff], // x
+    [0xff, 0xff, 0x39, 0x39, 0x39, 0x81, 0xf9, 0x83], // y
+    [0xff, 0xff, 0x01, 0xe3, 0xc7, 0x8f, 0x01, 0xff], // z
+    [0xf3, 0xe7, 0xe7, 0xcf, 0xe7, 0xe7, 0xf3, 0xff], // {
+    [0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0xe7, 0xff], // |
+    [0x9f, 0xcf, 0xcf, 0xe7, 0xcf, 0xcf, 0x9f, 0xff], // }
+    [0xff, 0xff, 0x8f, 0x45, 0xe3, 0xff, 0xff, 0xff], // ~
+    [0xc9, 0x36, 0x49, 0x36, 0x49, 0x36, 0x93, 0xff], // DEL
+];
@@ -1,207 +1,759 @@
+use crate::camera::Camera;
+use crate::contact::Contact;
+use crate::fixed::FixedPoint;
 use crate::gfx;
+use crate::level::{Level, START_LIVES};
+use crate::platform;
+use crate::platform::Platform;
 use crate::polygon::Point;
 use crate::rand_tab;
+use crate::replay::Replay;
+use crate::scene::Scene;
+use crate::scores::{Entry, HighScores};
 use crate::ship;
 use crate::surface;
-use crate::wasm4;
+use crate::wind::Wind;
 
 pub struct Game {
     frame_count: u32,
     prev_gamepad: u8,
-    is_game_over: bool,
-    is_title_screen: bool,
-    is_landed: bool,
+    scene: Scene,
     surface: surface::Surface,
     ship: ship::Ship,
+    camera: Camera,
     score: u32,
     stars: [Point; 60],
+    // Dust blowing past in the wind, the nearest layer of the parallax
+    dust: [Point; 16],
+    wind: Wind,
+    replay: Replay,
+    playback: Option<Replay>,
+    high_scores: HighScores,
+    initials: [u8; 3],
+    initials_pos: usize,
+    run_seed: u32,
+    landings: u16,
+    run_frames: u32,
+    sound: bool,
+    stability_assist: bool,
+    minimap: bool,
+    option_item: usize,
+    seed: u32,
+    pause_item: usize,
+    level: Level,
+    lives: u32,
+    intro_timer: u32,
 }
 
-const GRAV: f64 = 0.00070;
+// Frames the level intro card stays up for, unless skipped
+const INTRO_TIME: u32 = 120;
+const PAUSE_MENU: [&str; 3] = ["Resume", "Restart terrain", "Quit to title"];
+const OPTIONS: [&str; 3] = ["Sound", "Assist", "Minimap"];
+const OPTION_HELP: [&str; 3] = [
+    "Engine noise and\nlanding & crash\nsounds",
+    "Assist stops the\nship spinning when\nyou let go",
+    "Map of the whole\nworld across the\ntop of the screen",
+];
+// Throttle change per frame while up or down is held, & the HUD bar's height in pixels
+const THROTTLE_STEP: f64 = 0.02;
+const THROTTLE_BAR: u32 = 30;
+// How close to the edge of the world, in pixels, before the HUD warns about it
+const EDGE_WARNING: f64 = 60.0;
+// Wind speed that stretches the HUD's wind sock out flat
+const WIND_SOCK_FULL: f64 = 0.3;
 
 impl Game {
     pub fn new() -> Self {
+        let level = Level::new(1);
         let mut game = Game {
             frame_count: 0,
             prev_gamepad: 0,
-            is_game_over: false,
-            is_title_screen: true,
-            is_landed: false,
-            surface: surface::Surface::new(666666),
-            ship: ship::Ship::new(),
+            scene: Scene::Title,
+            surface: surface::Surface::new(666666, &level),
+            ship: ship::Ship::for_level(&level),
+            camera: Camera::new(),
             score: 0,
             stars: [Point::new(0.0, 0.0); 60],
+            dust: [Point::new(0.0, 0.0); 16],
+            wind: Wind::new(0.0, 0.0),
+            replay: Replay::new(666666, level.number),
+            playback: None,
+            high_scores: HighScores::new(),
+            initials: *b"AAA",
+            initials_pos: 0,
+            run_seed: 0,
+            landings: 0,
+            run_frames: 0,
+            sound: true,
+            stability_assist: true,
+            minimap: true,
+            option_item: 0,
+            seed: 666666,
+            pause_item: 0,
+            level,
+            lives: START_LIVES,
+            intro_timer: 0,
         };
 
         game.init_stars();
         game
     }
 
+    /// New attempt at the current level, on its fixed terrain or fresh terrain when it has none
     pub fn new_game(&mut self, score: u32) {
-        self.is_game_over = false;
-        self.is_landed = false;
+        self.new_game_seeded(score, self.level.seed.unwrap_or(self.frame_count));
+    }
+
+    pub fn new_game_seeded(&mut self, score: u32, seed: u32) {
         self.prev_gamepad = 0;
-        self.ship = ship::Ship::new();
-        self.surface = surface::Surface::new(self.frame_count);
-        self.ship.scale = 1.0;
+        self.ship = ship::Ship::for_level(&self.level);
+        self.ship.stability_assist = self.stability_assist;
+        self.surface = surface::Surface::new(seed, &self.level);
+        self.wind = Wind::for_level(&self.level);
+        self.seed = seed;
+        self.camera.reset(self.ship.pos);
         self.score = score;
         self.init_stars();
+        self.replay = Replay::new(seed, self.level.number);
+        self.replay.stability_assist = self.stability_assist;
+        self.playback = None;
+        self.update_ground();
     }
 
-    pub fn update(&mut self) {
-        self.frame_count += 1;
+    /// Start a new run straight away on the given terrain & campaign level, from whatever
+    /// scene the game is in. Levels with terrain of their own keep it whatever the seed
+    pub fn play_level(&mut self, seed: u32, level: u32) {
+        self.new_run(seed, level);
+        self.scene = Scene::Playing;
+    }
 
-        let pressed = self.input();
+    fn new_run(&mut self, seed: u32, level: u32) {
+        self.level = Level::new(level);
+        let seed = self.level.seed.unwrap_or(seed);
+        self.lives = START_LIVES;
+        self.new_game_seeded(0, seed);
+        self.run_seed = seed;
+        self.landings = 0;
+        self.run_frames = 0;
+    }
 
-        let mut scale = self.ship.pos.y / 50.0;
-        if scale > 2.0 {
-            scale = 2.0;
-        }
-        self.ship.scale = scale;
-        self.surface.scale = scale as f32;
+    /// Start a game on the replay's terrain, with flight input coming from the replay
+    pub fn play_replay(&mut self, replay: Replay) {
+        self.stability_assist = replay.stability_assist;
+        self.play_level(replay.seed, replay.level);
+        self.playback = Some(replay);
+    }
 
-        if self.is_title_screen {
-            if pressed & wasm4::BUTTON_1 != 0 || pressed & wasm4::BUTTON_2 != 0 {
-                self.new_game(0);
-                self.is_title_screen = false;
-            }
+    /// Recording of the current run, input is only captured while in flight
+    pub fn get_replay(&self) -> &Replay {
+        &self.replay
+    }
 
-            self.update_stars_title();
-            self.draw_stars();
+    pub fn get_score(&self) -> u32 {
+        self.score
+    }
 
-            gfx::shadow_text("** WASM LANDER ** ", 12, 15, 0x4, 0x2);
-            gfx::set_draw_color(0x2);
-            wasm4::line(0, 33, 160, 33);
-            wasm4::line(0, 92, 160, 92);
-            gfx::shadow_text("Press X for thrust", 8, 40, 0x4, 0x2);
-            gfx::shadow_text("Left/Right to turn", 8, 50, 0x4, 0x2);
-            gfx::shadow_text("Try to land on the\nlanding pads!", 8, 70, 0x4, 0x2);
-            gfx::shadow_text("PRESS BUTTON\n  TO START", 33, 130, 0x3, 0x2);
-            gfx::shadow_text("v12", 3, 150, 0x3, 0x2);
+    pub fn get_ship(&self) -> &ship::Ship {
+        &self.ship
+    }
 
-            return;
-        }
+    pub fn get_scene(&self) -> Scene {
+        self.scene
+    }
 
-        if self.is_game_over && !self.is_title_screen {
-            if pressed & wasm4::BUTTON_1 != 0 {
-                self.is_title_screen = true;
-                self.init_stars();
+    pub fn get_level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn get_lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// Wind speed where the ship is, positive blows to the right
+    pub fn get_wind(&self) -> f64 {
+        self.wind.at(self.ship.pos.y).to_f64()
+    }
+
+    pub fn update<P: Platform>(&mut self, p: &mut P) {
+        self.frame_count += 1;
+
+        let pressed = self.input(p);
+
+        self.update_scene(p, pressed);
+
+        // Flying is in the colours of the level's world, the menus stay on the Moon
+        let palette = match self.scene {
+            Scene::LevelIntro | Scene::Playing | Scene::Paused | Scene::Landed | Scene::Crashed => {
+                self.level.body.palette()
             }
+            _ => gfx::PALETTE,
+        };
+        p.set_palette(palette);
+        self.draw_scene(p);
+    }
 
-            self.draw_stars();
+    // Physics step for a frame of flight, move the ship, follow it with the camera, work
+    // out the ground under it then see what it hit
+    fn update_flight<P: Platform>(&mut self, p: &mut P) {
+        self.wind.update();
+        let wind = self.wind.at(self.ship.pos.y);
+        self.ship.update(
+            self.level.gravity,
+            self.level.body.drag(),
+            wind,
+            &self.surface,
+        );
+        self.update_ground();
+        self.ship.collide(&self.surface);
+
+        if self.ship.is_destroyed() {
+            self.lives -= 1;
+            if self.lives > 0 {
+                self.change_scene(p, Scene::Crashed);
+            } else {
+                self.change_scene(p, Scene::GameOver);
+            }
+        } else if self.ship.is_landed() {
+            self.change_scene(p, Scene::Landed);
+        }
+    }
 
-            gfx::shadow_text(&self.ship.crash_reason, 10, 30, 0x4, 0x2);
-            gfx::shadow_text("GAME OVER!", 40, 70, 0x3, 0x2);
-            gfx::shadow_text(
-                (String::from("Final score: ") + self.score.to_string().as_str()).as_str(),
-                10,
-                90,
-                0x4,
-                0x2,
+    fn update_ground(&mut self) {
+        // The zoom & the ship's spot on screen hold while it settles, so the pad doesn't
+        // move under it, but the rocking about its foot still has to show
+        if self.ship.is_settling() {
+            self.camera.hold(self.ship.pos);
+        } else {
+            self.camera.update(
+                self.ship.pos,
+                FixedPoint::from_point(self.ship.get_velocity()),
             );
-            return;
         }
+        self.ship.camera = self.camera;
+        self.surface.update(&self.camera);
+    }
 
-        if self.is_landed {
-            let score = self.score + self.ship.get_fuel() as u32;
+    fn change_scene<P: Platform>(&mut self, p: &mut P, next: Scene) {
+        debug_assert!(
+            self.scene.can_change_to(next),
+            "bad scene change {:?} -> {:?}",
+            self.scene,
+            next
+        );
+
+        self.exit_scene(p);
+        self.scene = next;
+        self.enter_scene(p);
+    }
 
-            if pressed & wasm4::BUTTON_1 != 0 {
-                self.new_game(score);
-                self.is_landed = false;
+    fn enter_scene<P: Platform>(&mut self, p: &mut P) {
+        match self.scene {
+            Scene::Title => self.init_stars(),
+            Scene::Landed => {
+                self.tone(p, 260, 80, 50, platform::TONE_TRIANGLE);
+                self.landings += 1;
+                self.trace_replay(p);
+            }
+            Scene::Crashed | Scene::GameOver => {
+                self.tone(p, 160, 50, 50, platform::TONE_NOISE);
+                self.trace_replay(p);
             }
+            Scene::LevelIntro => self.intro_timer = 0,
+            Scene::Paused => self.pause_item = 0,
+            Scene::Options => self.option_item = 0,
+            Scene::EnterInitials => self.initials_pos = 0,
+            Scene::HighScores => self.high_scores = HighScores::load(p),
+            Scene::Playing => {}
+        }
+    }
 
-            gfx::shadow_text("GREAT LANDING!", 30, 30, 0x4, 0x2);
-            gfx::shadow_text(
-                (String::from("Fuel left: ") + self.ship.get_fuel().round().to_string().as_str())
-                    .as_str(),
-                20,
-                50,
-                0x3,
-                0x2,
-            );
+    fn exit_scene<P: Platform>(&mut self, p: &mut P) {
+        if self.scene == Scene::EnterInitials {
+            self.high_scores.insert(Entry {
+                initials: self.initials,
+                score: self.score,
+                seed: self.run_seed,
+                landings: self.landings,
+                frames: self.run_frames,
+            });
+            self.high_scores.save(p);
+        }
+    }
 
-            gfx::shadow_text(
-                (String::from("Score: ") + score.to_string().as_str()).as_str(),
-                20,
-                70,
-                0x3,
-                0x2,
-            );
+    fn update_scene<P: Platform>(&mut self, p: &mut P, pressed: u8) {
+        match self.scene {
+            Scene::Title => {
+                self.update_stars_title();
+
+                if pressed & platform::BUTTON_1 != 0 || pressed & platform::BUTTON_2 != 0 {
+                    let seed = Level::new(1).seed.unwrap_or(self.frame_count);
+                    self.new_run(seed, 1);
+                    self.change_scene(p, Scene::LevelIntro);
+                } else if pressed & platform::BUTTON_UP != 0 {
+                    self.change_scene(p, Scene::HighScores);
+                } else if pressed & platform::BUTTON_DOWN != 0 {
+                    self.change_scene(p, Scene::Options);
+                }
+            }
+            Scene::Playing => {
+                if pressed & platform::BUTTON_2 != 0 {
+                    self.change_scene(p, Scene::Paused);
+                    return;
+                }
+
+                self.update_stars_flight();
+                self.run_frames += 1;
+                if self.ship.is_thrusting() {
+                    let fuel = self.ship.get_fuel();
+                    self.tone(p, (fuel + 80.0) as u32, 4, 20, platform::TONE_NOISE);
+                }
+                self.update_flight(p);
+            }
+            Scene::Paused => {
+                if pressed & platform::BUTTON_UP != 0 && self.pause_item > 0 {
+                    self.pause_item -= 1;
+                }
+                if pressed & platform::BUTTON_DOWN != 0 && self.pause_item < PAUSE_MENU.len() - 1 {
+                    self.pause_item += 1;
+                }
+
+                if pressed & platform::BUTTON_2 != 0 {
+                    self.change_scene(p, Scene::Playing);
+                } else if pressed & platform::BUTTON_1 != 0 {
+                    match self.pause_item {
+                        0 => self.change_scene(p, Scene::Playing),
+                        1 => {
+                            // Same terrain, with the score from before it was started
+                            self.new_game_seeded(self.score, self.seed);
+                            self.change_scene(p, Scene::Playing);
+                        }
+                        _ => self.change_scene(p, Scene::Title),
+                    }
+                }
+            }
+            Scene::LevelIntro => {
+                self.update_stars_title();
 
-            return;
+                self.intro_timer += 1;
+                if pressed & platform::BUTTON_1 != 0 || self.intro_timer >= INTRO_TIME {
+                    self.change_scene(p, Scene::Playing);
+                }
+            }
+            Scene::Landed => {
+                if pressed & platform::BUTTON_1 != 0 {
+                    self.level = self.level.next();
+                    self.new_game(self.landed_score());
+                    self.change_scene(p, Scene::LevelIntro);
+                }
+            }
+            Scene::Crashed => {
+                // Another go at the same level, on new terrain
+                if pressed & platform::BUTTON_1 != 0 {
+                    self.new_game(self.score);
+                    self.change_scene(p, Scene::LevelIntro);
+                }
+            }
+            Scene::GameOver => {
+                if pressed & platform::BUTTON_1 != 0 {
+                    self.high_scores = HighScores::load(p);
+                    if self.high_scores.qualifies(self.score) {
+                        self.change_scene(p, Scene::EnterInitials);
+                    } else {
+                        self.change_scene(p, Scene::Title);
+                    }
+                }
+            }
+            Scene::EnterInitials => {
+                let letter = &mut self.initials[self.initials_pos];
+                if pressed & platform::BUTTON_UP != 0 {
+                    *letter = if *letter >= b'Z' { b'A' } else { *letter + 1 };
+                }
+                if pressed & platform::BUTTON_DOWN != 0 {
+                    *letter = if *letter <= b'A' { b'Z' } else { *letter - 1 };
+                }
+                if pressed & platform::BUTTON_LEFT != 0 && self.initials_pos > 0 {
+                    self.initials_pos -= 1;
+                }
+                if pressed & platform::BUTTON_RIGHT != 0 && self.initials_pos < 2 {
+                    self.initials_pos += 1;
+                }
+
+                if pressed & platform::BUTTON_1 != 0 {
+                    self.change_scene(p, Scene::HighScores);
+                }
+            }
+            Scene::HighScores => {
+                if pressed & platform::BUTTON_1 != 0 || pressed & platform::BUTTON_2 != 0 {
+                    self.change_scene(p, Scene::Title);
+                }
+            }
+            Scene::Options => {
+                if pressed & platform::BUTTON_UP != 0 && self.option_item > 0 {
+                    self.option_item -= 1;
+                }
+                if pressed & platform::BUTTON_DOWN != 0 && self.option_item < OPTIONS.len() - 1 {
+                    self.option_item += 1;
+                }
+                if pressed & platform::BUTTON_1 != 0 {
+                    match self.option_item {
+                        0 => self.sound = !self.sound,
+                        1 => self.stability_assist = !self.stability_assist,
+                        _ => self.minimap = !self.minimap,
+                    }
+                }
+                if pressed & platform::BUTTON_2 != 0 {
+                    self.change_scene(p, Scene::Title);
+                }
+            }
         }
+    }
 
-        // Update stars based on ship position
-        let mut i = 0;
-        for star in self.stars.iter_mut() {
-            star.x -= (self.ship.get_velocity().x * 0.20)
-                * (1.0 / self.surface.scale as f64)
-                * (i % 4) as f64;
-            star.y -= (self.ship.get_velocity().y * 0.20)
-                * (1.0 / self.surface.scale as f64)
-                * (i % 4) as f64;
-            if star.x < 0.0 {
-                star.x = 160.0;
+    fn draw_scene<P: Platform>(&mut self, p: &mut P) {
+        match self.scene {
+            Scene::Title => {
+                self.draw_stars(p);
+
+                gfx::shadow_text(p, "** WASM LANDER ** ", 12, 15, 0x4, 0x2);
+                p.set_draw_color(0x2);
+                p.line(0, 33, 160, 33);
+                p.line(0, 92, 160, 92);
+                gfx::shadow_text(p, "Press X for thrust", 8, 40, 0x4, 0x2);
+                gfx::shadow_text(p, "Left/Right to turn", 8, 50, 0x4, 0x2);
+                gfx::shadow_text(p, "Try to land on the\nlanding pads!", 8, 70, 0x4, 0x2);
+                gfx::shadow_text(p, "Up: High scores\nDown: Options", 8, 100, 0x3, 0x2);
+                gfx::shadow_text(p, "PRESS BUTTON\n  TO START", 33, 130, 0x3, 0x2);
+                gfx::shadow_text(p, "v12", 3, 150, 0x3, 0x2);
             }
-            if star.x > 160.0 {
-                star.x = 0.0;
+            Scene::Playing => {
+                self.draw_stars(p);
+                self.draw_dust(p);
+                self.surface.draw(p);
+                self.ship.draw(p);
+                self.draw_hud(p);
             }
-            if star.y < 0.0 {
-                star.y = 160.0;
+            Scene::Paused => {
+                self.draw_stars(p);
+                self.draw_dust(p);
+                self.surface.draw(p);
+                self.ship.draw(p);
+                self.draw_hud(p);
+                self.draw_pause_menu(p);
             }
-            if star.y > 160.0 {
-                star.y = 0.0;
+            Scene::Landed => {
+                gfx::shadow_text(p, "GREAT LANDING!", 30, 30, 0x4, 0x2);
+
+                let (fuel, multiplier, bonus) = self.landing_points();
+                let lines = [
+                    String::from("Fuel left: ") + fuel.to_string().as_str(),
+                    String::from("Pad:       x") + multiplier.to_string().as_str(),
+                    String::from("Precision: ") + bonus.to_string().as_str(),
+                ];
+                for (i, line) in lines.iter().enumerate() {
+                    gfx::shadow_text(p, line.as_str(), 20, 50 + i as i32 * 12, 0x3, 0x2);
+                }
+
+                gfx::shadow_text(
+                    p,
+                    (String::from("Score: ") + self.landed_score().to_string().as_str()).as_str(),
+                    20,
+                    94,
+                    0x4,
+                    0x2,
+                );
             }
-            i += 1;
+            Scene::LevelIntro => {
+                self.draw_stars(p);
+                self.draw_level_intro(p);
+            }
+            Scene::Crashed => {
+                self.draw_stars(p);
+
+                let reason = self.ship.contact.describe(&self.level);
+                gfx::shadow_text(p, reason.as_str(), 10, 30, 0x4, 0x2);
+                gfx::shadow_text(
+                    p,
+                    (String::from("Lives left: ") + self.lives.to_string().as_str()).as_str(),
+                    10,
+                    70,
+                    0x3,
+                    0x2,
+                );
+                gfx::shadow_text(p, "Press X to retry", 10, 90, 0x4, 0x2);
+            }
+            Scene::GameOver => {
+                self.draw_stars(p);
+
+                let reason = self.ship.contact.describe(&self.level);
+                gfx::shadow_text(p, reason.as_str(), 10, 30, 0x4, 0x2);
+                gfx::shadow_text(p, "GAME OVER!", 40, 70, 0x3, 0x2);
+                gfx::shadow_text(
+                    p,
+                    (String::from("Final score: ") + self.score.to_string().as_str()).as_str(),
+                    10,
+                    90,
+                    0x4,
+                    0x2,
+                );
+            }
+            Scene::EnterInitials => {
+                self.draw_stars(p);
+                self.draw_initials(p);
+            }
+            Scene::HighScores => {
+                self.draw_stars(p);
+                self.draw_high_scores(p);
+            }
+            Scene::Options => {
+                self.draw_stars(p);
+
+                gfx::shadow_text(p, "OPTIONS", 52, 15, 0x4, 0x2);
+                let on_off = |on: bool| if on { "ON" } else { "OFF" };
+                let values = [
+                    on_off(self.sound),
+                    on_off(self.stability_assist),
+                    on_off(self.minimap),
+                ];
+                for (i, item) in OPTIONS.iter().enumerate() {
+                    let y = 50 + i as i32 * 12;
+                    let line = String::from(*item) + ": " + values[i];
+                    if i == self.option_item {
+                        gfx::shadow_text(p, ">", 8, y, 0x4, 0x2);
+                        gfx::shadow_text(p, line.as_str(), 20, y, 0x4, 0x2);
+                    } else {
+                        gfx::shadow_text(p, line.as_str(), 20, y, 0x3, 0x2);
+                    }
+                }
+                gfx::shadow_text(p, OPTION_HELP[self.option_item], 8, 90, 0x3, 0x2);
+                gfx::shadow_text(p, "Up/Down: choose\nX: change\nZ: back", 8, 120, 0x3, 0x2);
+            }
+        }
+    }
+
+    fn draw_pause_menu<P: Platform>(&mut self, p: &mut P) {
+        p.set_draw_color(0x21);
+        p.rect(8, 50, 144, 62);
+        gfx::shadow_text(p, "PAUSED", 56, 56, 0x4, 0x2);
+
+        for (i, item) in PAUSE_MENU.iter().enumerate() {
+            let y = 74 + i as i32 * 12;
+            if i == self.pause_item {
+                gfx::shadow_text(p, ">", 12, y, 0x4, 0x2);
+                gfx::shadow_text(p, item, 24, y, 0x4, 0x2);
+            } else {
+                gfx::shadow_text(p, item, 24, y, 0x3, 0x2);
+            }
+        }
+    }
+
+    fn draw_level_intro<P: Platform>(&mut self, p: &mut P) {
+        let level = self.level;
+        let title = String::from("LEVEL ") + level.number.to_string().as_str();
+        gfx::shadow_text(p, title.as_str(), 80 - title.len() as i32 * 4, 20, 0x4, 0x2);
+
+        // Gravity is shown relative to the first level, which is lunar gravity
+        let gravity = gfx::whole(level.gravity / Level::new(1).gravity * 100.0);
+        let lines = [
+            String::from("World:     ") + level.body.name(),
+            String::from("Gravity:   ") + gravity.as_str() + "%",
+            String::from("Fuel:      ") + gfx::whole(level.fuel).as_str(),
+            String::from("Pads:      ") + level.pad_count.to_string().as_str(),
+            String::from("Max speed: ") + gfx::tenths(level.max_speed).as_str(),
+            String::from("Lives:     ") + self.lives.to_string().as_str(),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            gfx::shadow_text(p, line.as_str(), 8, 50 + i as i32 * 12, 0x3, 0x2);
         }
-        self.draw_stars();
 
-        self.ship.update(GRAV);
-        self.surface
-            .draw(self.ship.get_pos().x as f32, self.ship.get_pos().y as f32);
-        self.ship.draw(&self.surface);
+        gfx::shadow_text(p, "GET READY!", 40, 130, 0x4, 0x2);
+    }
 
+    fn draw_hud<P: Platform>(&mut self, p: &mut P) {
         let angle_str =
-            String::from("A: ") + (self.ship.angle.to_degrees() + 90.0).to_string().as_str();
-        let fuel_str = String::from("F: ") + self.ship.get_fuel().round().to_string().as_str();
-        let speed_str =
-            String::from("S: ") + (self.ship.get_speed() * 100.0).round().to_string().as_str();
-        gfx::shadow_text(fuel_str.as_str(), 0, 0, 0x3, 0x2);
-        gfx::shadow_text(speed_str.as_str(), 0, 10, 0x3, 0x2);
-        gfx::shadow_text(angle_str.as_str(), 90, 0, 0x3, 0x2);
+            String::from("A: ") + gfx::whole(self.ship.angle.to_f64().to_degrees() + 90.0).as_str();
+        let fuel_str = String::from("F: ") + gfx::whole(self.ship.get_fuel()).as_str();
+        let speed_str = String::from("S: ") + gfx::whole(self.ship.get_speed() * 100.0).as_str();
+        gfx::shadow_text(p, fuel_str.as_str(), 0, 0, 0x3, 0x2);
+        gfx::shadow_text(p, speed_str.as_str(), 0, 10, 0x3, 0x2);
+        gfx::shadow_text(p, angle_str.as_str(), 90, 0, 0x3, 0x2);
+
+        let level_str = String::from("L") + self.level.number.to_string().as_str();
+        let lives_str = String::from("x") + self.lives.to_string().as_str();
+        gfx::shadow_text(p, level_str.as_str(), 90, 10, 0x3, 0x2);
+        gfx::shadow_text(p, lives_str.as_str(), 130, 10, 0x3, 0x2);
+
+        self.surface.draw_pad_pointers(p);
+
+        // The minimap sits under the text, pushing the rest of the HUD down
+        let mut top = 22;
+        if self.minimap {
+            self.surface.draw_minimap(p, top - 2);
+            top += 12;
+        }
 
-        if self.ship.destroyed {
-            wasm4::tone(160, 50, 50, wasm4::TONE_NOISE);
-            self.is_game_over = true;
+        // Wind sock on a pole under the speed, on windy levels. It hangs limp in a lull
+        // & flies straight out from the pole in a gale
+        if !self.wind.is_calm() {
+            let wind = self.wind.at(self.ship.pos.y).to_f64();
+            let strength = (wind.abs() / WIND_SOCK_FULL).min(1.0);
+            let sock_x = 4 + (wind.signum() * strength * 8.0).round() as i32;
+            let sock_y = top + ((1.0 - strength) * 6.0).round() as i32;
+            p.set_draw_color(2);
+            p.vline(3, top, 12);
+            p.set_draw_color(4);
+            p.line(4, top, sock_x, sock_y);
         }
 
-        if self.ship.landed {
-            wasm4::tone(260, 80, 50, wasm4::TONE_TRIANGLE);
-            self.is_landed = true;
+        self.draw_edge_warning(p, top);
+
+        // Throttle bar down the right edge, filling up from the bottom
+        let fill = (self.ship.get_throttle() * THROTTLE_BAR as f64).round() as u32;
+        p.set_draw_color(0x20);
+        p.rect(152, top, 6, THROTTLE_BAR + 2);
+        if fill > 0 {
+            p.set_draw_color(0x4);
+            p.rect(153, top + 1 + (THROTTLE_BAR - fill) as i32, 4, fill);
         }
     }
 
-    pub fn input(&mut self) -> u8 {
-        let gamepad = unsafe { *wasm4::GAMEPAD1 };
-        let just_pressed = gamepad & (gamepad ^ self.prev_gamepad);
+    // Flashing warning near the edge of the world, with an arrow back to the nearest pad
+    fn draw_edge_warning<P: Platform>(&mut self, p: &mut P, top: i32) {
+        let x = self.ship.get_pos().x;
+        let (left, right) = self.surface.bounds();
+        let near = x - left.to_f64() < EDGE_WARNING || right.to_f64() - x < EDGE_WARNING;
+        if !near || (self.frame_count / 10).is_multiple_of(2) {
+            return;
+        }
 
-        self.ship.set_engines(false);
-        if gamepad & wasm4::BUTTON_1 != 0 {
-            self.ship.set_engines(true);
+        gfx::shadow_text(p, "EDGE", 64, top, 0x4, 0x2);
+        if let Some(pad_x) = self.surface.nearest_pad(x) {
+            let dir = if pad_x < x { -1 } else { 1 };
+            let y = top + 12;
+            p.set_draw_color(4);
+            p.line(80 - dir * 7, y, 80 + dir * 7, y);
+            p.line(80 + dir * 7, y, 80 + dir * 3, y - 4);
+            p.line(80 + dir * 7, y, 80 + dir * 3, y + 4);
         }
+    }
+
+    // Score including the fuel bonus for the landing just made
+    fn landed_score(&self) -> u32 {
+        let (fuel, multiplier, bonus) = self.landing_points();
+        self.score + fuel * multiplier + bonus
+    }
 
-        if gamepad & wasm4::BUTTON_RIGHT != 0 {
-            self.ship.angle += 0.03 * (0.5 / self.ship.scale);
+    // Fuel left, the pad's multiplier & the precision bonus
+    fn landing_points(&self) -> (u32, u32, u32) {
+        let (multiplier, bonus) = match self.ship.contact {
+            Contact::Landed { pad, score_quality } => (pad.multiplier, score_quality),
+            _ => (1, 0),
+        };
+
+        (self.ship.get_fuel() as u32, multiplier, bonus)
+    }
+
+    fn tone<P: Platform>(&self, p: &mut P, frequency: u32, duration: u32, volume: u32, flags: u32) {
+        if self.sound {
+            p.tone(frequency, duration, volume, flags);
         }
+    }
 
-        if gamepad & wasm4::BUTTON_LEFT != 0 {
-            self.ship.angle -= 0.03 * (0.5 / self.ship.scale);
+    // Make the run available from the debug console, so it can be shared. The hex goes
+    // on a line of its own, so there's only the one copy of it on the heap
+    fn trace_replay<P: Platform>(&self, p: &mut P) {
+        p.trace("replay:");
+        p.trace(self.replay.to_hex().as_str());
+    }
+
+    pub fn input<P: Platform>(&mut self, p: &P) -> u8 {
+        let mut gamepad = p.gamepad();
+
+        if self.scene == Scene::Playing {
+            if let Some(playback) = &self.playback {
+                gamepad = playback.get(self.replay.len());
+            }
+            // Pausing isn't part of the flight, the frame it's pressed on doesn't move the
+            // ship & the button is left out so a replay never pauses itself
+            let pausing = gamepad & !self.prev_gamepad & platform::BUTTON_2 != 0;
+            if !pausing {
+                self.replay.record(gamepad & !platform::BUTTON_2);
+            }
+        }
+
+        let just_pressed = gamepad & (gamepad ^ self.prev_gamepad);
+
+        // The ship is only flown while playing, other scenes just use the presses
+        if self.scene == Scene::Playing && !self.ship.is_settling() {
+            self.ship.set_engines(false);
+            if gamepad & platform::BUTTON_1 != 0 {
+                self.ship.set_engines(true);
+            }
+
+            let mut rcs = 0;
+            if gamepad & platform::BUTTON_RIGHT != 0 {
+                rcs += 1;
+            }
+            if gamepad & platform::BUTTON_LEFT != 0 {
+                rcs -= 1;
+            }
+            self.ship.set_rcs(rcs);
+
+            // Up & down are held to open & close the throttle
+            let throttle = self.ship.get_throttle();
+            if gamepad & platform::BUTTON_UP != 0 {
+                self.ship.set_throttle(throttle + THROTTLE_STEP);
+            }
+            if gamepad & platform::BUTTON_DOWN != 0 {
+                self.ship.set_throttle(throttle - THROTTLE_STEP);
+            }
         }
 
         self.prev_gamepad = gamepad;
         just_pressed
     }
 
+    fn draw_initials<P: Platform>(&mut self, p: &mut P) {
+        gfx::shadow_text(p, "NEW HIGH SCORE!", 20, 20, 0x4, 0x2);
+        gfx::shadow_text(
+            p,
+            (String::from("Score: ") + self.score.to_string().as_str()).as_str(),
+            20,
+            40,
+            0x3,
+            0x2,
+        );
+        gfx::shadow_text(p, "Enter initials", 20, 60, 0x3, 0x2);
+
+        for (i, c) in self.initials.iter().enumerate() {
+            let x = 56 + i as i32 * 16;
+            gfx::shadow_text(p, (*c as char).to_string().as_str(), x, 85, 0x4, 0x2);
+            if i == self.initials_pos {
+                p.set_draw_color(0x4);
+                p.hline(x, 95, 8);
+            }
+        }
+
+        gfx::shadow_text(p, "Up/Down letter\nX to save", 20, 125, 0x3, 0x2);
+    }
+
+    fn draw_high_scores<P: Platform>(&mut self, p: &mut P) {
+        gfx::shadow_text(p, "HIGH SCORES", 36, 10, 0x4, 0x2);
+
+        if self.high_scores.entries().is_empty() {
+            gfx::shadow_text(p, "No scores yet", 28, 70, 0x3, 0x2);
+            return;
+        }
+
+        for (i, e) in self.high_scores.entries().iter().enumerate() {
+            let line = format!(
+                "{:>2} {} {:>6}",
+                i + 1,
+                String::from_utf8_lossy(&e.initials),
+                e.score
+            );
+            gfx::shadow_text(p, line.as_str(), 20, 30 + i as i32 * 12, 0x3, 0x2);
+        }
+    }
+
     fn init_stars(&mut self) {
         let mut i = 0;
         while i < self.stars.len() {
@@ -210,19 +762,64 @@ impl Game {
             star.y = rand_tab::f64() * 160.0;
             i += 1;
         }
+        for speck in self.dust.iter_mut() {
+            speck.x = rand_tab::f64() * 160.0;
+            speck.y = rand_tab::f64() * 160.0;
+        }
+    }
+
+    fn draw_dust<P: Platform>(&mut self, p: &mut P) {
+        if self.wind.is_calm() {
+            return;
+        }
+
+        p.set_draw_color(3);
+        for speck in self.dust.iter() {
+            p.hline(speck.x as i32, speck.y as i32, 2);
+        }
     }
 
-    fn draw_stars(&mut self) {
+    fn draw_stars<P: Platform>(&mut self, p: &mut P) {
         // Draw stars
         let mut i = 0;
         while i < self.stars.len() {
             let star = self.stars[i];
-            gfx::set_draw_color(((i % 4) + 1) as u16);
-            wasm4::rect(star.x as i32, star.y as i32, 1, 1);
+            p.set_draw_color(((i % 4) + 1) as u16);
+            p.rect(star.x as i32, star.y as i32, 1, 1);
             i += 1;
         }
     }
 
+    // Stars move against the ship's velocity through the air, so they drift with the
+    // wind, nearer ones faster
+    fn update_stars_flight(&mut self) {
+        let velocity = self.ship.get_velocity();
+        let wind = self.wind.at(self.ship.pos.y).to_f64();
+        let scale = self.camera.zoom.to_f64();
+        for (i, star) in self.stars.iter_mut().enumerate() {
+            star.x -= ((velocity.x - wind) * 0.20) * (1.0 / scale) * (i % 4) as f64;
+            star.y -= (velocity.y * 0.20) * (1.0 / scale) * (i % 4) as f64;
+            if star.x < 0.0 {
+                star.x = 160.0;
+            }
+            if star.x > 160.0 {
+                star.x = 0.0;
+            }
+            if star.y < 0.0 {
+                star.y = 160.0;
+            }
+            if star.y > 160.0 {
+                star.y = 0.0;
+            }
+        }
+
+        // Dust is close by, so it sweeps past fastest
+        for speck in self.dust.iter_mut() {
+            speck.x = wrap(speck.x + (wind - velocity.x) / scale);
+            speck.y = wrap(speck.y - velocity.y / scale);
+        }
+    }
+
     fn update_stars_title(&mut self) {
         let mut i = 0;
         while i < self.stars.len() {
@@ -242,3 +839,14 @@ impl Game {
         }
     }
 }
+
+// Back onto the screen from the other side, like rem_euclid without the cart needing fmod
+fn wrap(v: f64) -> f64 {
+    v - (v / 160.0).floor() * 160.0
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,18 +1,27 @@
-use crate::wasm4;
+use crate::platform::Platform;
 
-pub fn set_palette(palette: [u32; 4]) {
-    unsafe {
-        *wasm4::PALETTE = palette;
-    }
+pub const PALETTE: [u32; 4] = [0x000000, 0x004400, 0x008800, 0x00ff00];
+
+// Numbers go on screen through integers, Rust's float formatting is too big for the cart
+
+/// Rounded to a whole number
+pub fn whole(v: f64) -> String {
+    (v.round() as i64).to_string()
 }
 
-pub fn set_draw_color(idx: u16) {
-    unsafe { *wasm4::DRAW_COLORS = idx }
+/// Rounded to one decimal place
+pub fn tenths(v: f64) -> String {
+    let tenths = (v * 10.0).round() as i64;
+    let sign = if tenths < 0 { "-" } else { "" };
+    String::from(sign)
+        + (tenths.abs() / 10).to_string().as_str()
+        + "."
+        + (tenths.abs() % 10).to_string().as_str()
 }
 
-pub fn shadow_text(text_v: &str, x: i32, y: i32, sc: u16, dc: u16) {
-    set_draw_color(dc);
-    wasm4::text(text_v, x - 1, y + 1);
-    set_draw_color(sc);
-    wasm4::text(text_v, x, y);
+pub fn shadow_text<P: Platform>(p: &mut P, text_v: &str, x: i32, y: i32, sc: u16, dc: u16) {
+    p.set_draw_color(dc);
+    p.text(text_v, x - 1, y + 1);
+    p.set_draw_color(sc);
+    p.text(text_v, x, y);
 }
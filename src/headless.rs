@@ -0,0 +1,117 @@
+use crate::platform::Platform;
+
+// WASM-4 only gives carts 1 KiB of disk
+pub const DISK_SIZE: usize = 1024;
+
+/// A single call made by the game into the platform
+#[derive(Clone, Debug, PartialEq)]
+pub enum Call {
+    Palette([u32; 4]),
+    DrawColor(u16),
+    Line(i32, i32, i32, i32),
+    Rect(i32, i32, u32, u32),
+    Oval(i32, i32, u32, u32),
+    HLine(i32, i32, u32),
+    VLine(i32, i32, u32),
+    Text(String, i32, i32),
+    Tone(u32, u32, u32, u32),
+    Trace(String),
+}
+
+/// In-memory backend that records every call, for running the game natively
+pub struct Headless {
+    pub gamepad: u8,
+    pub calls: Vec<Call>,
+    pub disk: Vec<u8>,
+}
+
+impl Headless {
+    pub fn new() -> Self {
+        Self {
+            gamepad: 0,
+            calls: Vec::new(),
+            disk: Vec::new(),
+        }
+    }
+
+    /// Forget the recorded calls, normally done between frames
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.calls.iter().filter_map(|c| match c {
+            Call::Text(t, _, _) => Some(t.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn tones(&self) -> impl Iterator<Item = &Call> {
+        self.calls.iter().filter(|c| matches!(c, Call::Tone(..)))
+    }
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Platform for Headless {
+    fn set_palette(&mut self, palette: [u32; 4]) {
+        self.calls.push(Call::Palette(palette));
+    }
+
+    fn set_draw_color(&mut self, idx: u16) {
+        self.calls.push(Call::DrawColor(idx));
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.calls.push(Call::Line(x1, y1, x2, y2));
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        self.calls.push(Call::Rect(x, y, width, height));
+    }
+
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        self.calls.push(Call::Oval(x, y, width, height));
+    }
+
+    fn hline(&mut self, x: i32, y: i32, len: u32) {
+        self.calls.push(Call::HLine(x, y, len));
+    }
+
+    fn vline(&mut self, x: i32, y: i32, len: u32) {
+        self.calls.push(Call::VLine(x, y, len));
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32) {
+        self.calls.push(Call::Text(String::from(text), x, y));
+    }
+
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32) {
+        self.calls
+            .push(Call::Tone(frequency, duration, volume, flags));
+    }
+
+    fn gamepad(&self) -> u8 {
+        self.gamepad
+    }
+
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32 {
+        let n = dest.len().min(self.disk.len());
+        dest[..n].copy_from_slice(&self.disk[..n]);
+        n as u32
+    }
+
+    fn disk_write(&mut self, src: &[u8]) -> u32 {
+        let n = src.len().min(DISK_SIZE);
+        self.disk = src[..n].to_vec();
+        n as u32
+    }
+
+    fn trace(&mut self, text: &str) {
+        self.calls.push(Call::Trace(String::from(text)));
+    }
+}
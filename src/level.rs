@@ -0,0 +1,70 @@
+// Campaign levels, each one a bit harder than the last. They're described in
+// assets/levels.txt, which build.rs turns into the LEVELS table at compile time
+
+use crate::body::Body;
+use crate::polygon::Point;
+
+pub const START_LIVES: u32 = 3;
+pub const MAX_PADS: usize = 4;
+
+include!(concat!(env!("OUT_DIR"), "/levels.rs"));
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Pad {
+    /// Position in terrain noise units, None to place it randomly
+    pub x: Option<f32>,
+    pub width: f32,
+    pub multiplier: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Level {
+    pub number: u32,
+    /// World the level is on
+    pub body: Body,
+    /// Fixed terrain seed, None for new terrain every attempt
+    pub seed: Option<u32>,
+    pub gravity: f64,
+    pub fuel: f64,
+    pub pads: [Pad; MAX_PADS],
+    pub pad_count: usize,
+    /// Terrain height & how stretched out it is, lower smooth gives rougher ground
+    pub high: f32,
+    pub smooth: f32,
+    pub start_pos: Point,
+    pub start_velocity: Point,
+    /// Landing limits, speed in HUD units, angle in degrees off vertical
+    pub max_speed: f64,
+    pub max_angle: f64,
+    pub max_drift: f64,
+    /// Steady wind speed & gust strength, in pixels per frame
+    pub wind: f64,
+    pub gust: f64,
+}
+
+impl Level {
+    /// Level by number starting at 1, past the end of the campaign the last level repeats.
+    /// The table is for the Moon, so the level's world scales the gravity & terrain
+    pub fn new(number: u32) -> Self {
+        let number = number.max(1);
+        let idx = (number as usize).min(LEVELS.len()) - 1;
+        let level = LEVELS[idx];
+        let (high, smooth) = level.body.terrain();
+
+        Level {
+            number,
+            gravity: level.gravity * level.body.gravity(),
+            high: level.high * high,
+            smooth: level.smooth * smooth,
+            ..level
+        }
+    }
+
+    pub fn next(&self) -> Level {
+        Level::new(self.number + 1)
+    }
+
+    pub fn pads(&self) -> &[Pad] {
+        &self.pads[..self.pad_count]
+    }
+}
@@ -1,31 +1,54 @@
-#[cfg(feature = "buddy-alloc")]
 // Game modules
-mod game;
-mod gfx;
+pub mod body;
+pub mod camera;
+pub mod contact;
+pub mod fixed;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod framebuffer;
+pub mod game;
+pub mod gfx;
+pub mod headless;
+pub mod level;
+mod noise;
 mod particle;
-mod polygon;
+pub mod platform;
+#[cfg(not(target_arch = "wasm32"))]
+mod png;
+pub mod polygon;
 mod rand_tab;
-mod ship;
-mod surface;
+pub mod replay;
+pub mod scene;
+pub mod scores;
+pub mod ship;
+pub mod surface;
+pub mod wind;
 
 // These were auto-generated by WASM4
+#[cfg(all(feature = "buddy-alloc", target_arch = "wasm32"))]
 mod alloc;
+#[cfg(target_arch = "wasm32")]
 mod wasm4;
 
+#[cfg(target_arch = "wasm32")]
 use lazy_static::lazy_static;
+#[cfg(target_arch = "wasm32")]
 use std::sync::Mutex;
 
+#[cfg(target_arch = "wasm32")]
 lazy_static! {
     static ref GAME: Mutex<game::Game> = Mutex::new(game::Game::new());
 }
 
+#[cfg(target_arch = "wasm32")]
 #[no_mangle]
 fn start() {
-    gfx::set_palette([0x000000, 0x004400, 0x008800, 0x00ff00]);
+    use platform::Platform;
+    platform::Wasm4.set_palette(gfx::PALETTE);
     GAME.lock().expect("").new_game(0);
 }
 
+#[cfg(target_arch = "wasm32")]
 #[no_mangle]
 fn update() {
-    GAME.lock().expect("").update();
+    GAME.lock().expect("").update(&mut platform::Wasm4);
 }
@@ -0,0 +1,73 @@
+// Perlin noise for the terrain, worked the same way as noilib-simple's but in fixed
+// point with the gradients from the sine table. The ground is what the ship collides
+// with, so it has to come out the same to the bit on every runtime
+
+use crate::fixed::Fixed;
+
+// Hashes are taken modulo this, then spread over four turns for a gradient's direction
+const DIRECTIONS: i64 = 123456;
+const TURNS: i32 = 8;
+const SQRT_2: Fixed = Fixed::from_f64(std::f64::consts::SQRT_2);
+
+pub struct Noise {
+    permutations: [i32; 256],
+}
+
+impl Noise {
+    pub fn new(seed: u64) -> Self {
+        const A: u64 = 6364136223846793005;
+        const C: u64 = 1442695040888963407;
+        let mut seed = seed;
+        let mut permutations = [0i32; 256];
+        for p in permutations.iter_mut() {
+            seed = A.wrapping_mul(seed).wrapping_add(C);
+            *p = seed as i32;
+        }
+
+        Self { permutations }
+    }
+
+    // Unit gradient at a corner of the grid
+    fn gradient(&self, x: i32, y: i32) -> (Fixed, Fixed) {
+        let sum = y.wrapping_mul(506791837).wrapping_add(x);
+        let hash = (0..4).fold(0i32, |hash, byte| {
+            hash.wrapping_add(self.permutations[((sum >> (byte * 8)) & 0xff) as usize])
+        });
+
+        // Share of the way round, which is negative for negative hashes
+        let share = Fixed::from_bits((((hash as i64 % DIRECTIONS) << 16) / DIRECTIONS) as i32);
+        let dir = share * Fixed::PI * Fixed::from_int(TURNS);
+        (dir.cos(), dir.sin())
+    }
+
+    fn dot_gradient(&self, xi: i32, yi: i32, x: Fixed, y: Fixed) -> Fixed {
+        let (gx, gy) = self.gradient(xi, yi);
+        (Fixed::from_int(xi) - x) * gx + (Fixed::from_int(yi) - y) * gy
+    }
+
+    /// Noise at a point, roughly -1 to 1
+    pub fn perlin(&self, x: Fixed, y: Fixed) -> Fixed {
+        let (xf, yf) = (x.floor(), y.floor());
+        let xo = x - Fixed::from_int(xf);
+        let yo = y - Fixed::from_int(yf);
+
+        let top = smoothstep(
+            self.dot_gradient(xf, yf, x, y),
+            self.dot_gradient(xf + 1, yf, x, y),
+            xo,
+        );
+        let bottom = smoothstep(
+            self.dot_gradient(xf, yf + 1, x, y),
+            self.dot_gradient(xf + 1, yf + 1, x, y),
+            xo,
+        );
+        smoothstep(top, bottom, yo) * SQRT_2
+    }
+}
+
+// From a to b along an S curve that's flat at both ends
+fn smoothstep(a: Fixed, b: Fixed, t: Fixed) -> Fixed {
+    let ease =
+        (t * (t * Fixed::from_int(6) - Fixed::from_int(15)) + Fixed::from_int(10)) * t * t * t;
+    (b - a) * ease + a
+}
@@ -1,21 +1,23 @@
-use crate::gfx;
+use crate::camera::Camera;
+use crate::fixed::Fixed;
+use crate::platform::Platform;
 use crate::polygon::Point;
-use crate::wasm4;
 
 #[derive(Copy, Clone)]
 pub struct Particle {
+    /// Position in the world, so particles stay where they were left as the view moves
     pub pos: Point,
-    angle: f64,
-    speed: f64,
+    velocity: Point,
     lifetime: f64,
 }
 
 impl Particle {
     pub fn new(x: f64, y: f64, angle: f64, speed: f64, lifetime: f64) -> Self {
+        // Direction from the sine table, the cart doesn't carry float trig
+        let angle = Fixed::from_f64(angle);
         Self {
             pos: Point::new(x, y),
-            angle,
-            speed,
+            velocity: Point::new(angle.cos().to_f64() * speed, angle.sin().to_f64() * speed),
             lifetime,
         }
     }
@@ -23,19 +25,20 @@ impl Particle {
     pub fn update(&mut self) {
         self.lifetime -= 1.0;
 
-        self.pos.x += self.angle.cos() * self.speed;
-        self.pos.y += self.angle.sin() * self.speed;
+        self.pos.x += self.velocity.x;
+        self.pos.y += self.velocity.y;
     }
 
     pub fn is_dead(&self) -> bool {
         self.lifetime <= 0.0
     }
 
-    pub fn draw(&self, color: u16) {
+    pub fn draw<P: Platform>(&self, p: &mut P, camera: &Camera, color: u16) {
         if self.is_dead() {
             return;
         }
-        gfx::set_draw_color(color);
-        wasm4::rect(self.pos.x as i32, self.pos.y as i32, 1, 1);
+        let at = camera.to_screen(self.pos);
+        p.set_draw_color(color);
+        p.rect(at.x as i32, at.y as i32, 1, 1);
     }
 }
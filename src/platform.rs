@@ -0,0 +1,102 @@
+// Everything the game needs from the outside world goes through this trait, so the
+// same game code can drive the real WASM-4 console or a native backend
+
+pub const SCREEN_SIZE: i32 = 160;
+
+// Gamepad bits & tone flags, these use the WASM-4 encodings so backends can pass them straight through
+pub const BUTTON_1: u8 = 1;
+pub const BUTTON_2: u8 = 2;
+pub const BUTTON_LEFT: u8 = 16;
+pub const BUTTON_RIGHT: u8 = 32;
+pub const BUTTON_UP: u8 = 64;
+pub const BUTTON_DOWN: u8 = 128;
+
+pub const TONE_PULSE1: u32 = 0;
+pub const TONE_PULSE2: u32 = 1;
+pub const TONE_TRIANGLE: u32 = 2;
+pub const TONE_NOISE: u32 = 3;
+
+pub trait Platform {
+    fn set_palette(&mut self, palette: [u32; 4]);
+    fn set_draw_color(&mut self, idx: u16);
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32);
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32);
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32);
+    fn hline(&mut self, x: i32, y: i32, len: u32);
+    fn vline(&mut self, x: i32, y: i32, len: u32);
+    fn text(&mut self, text: &str, x: i32, y: i32);
+
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32);
+
+    /// Current state of the first gamepad, see the BUTTON_* bits
+    fn gamepad(&self) -> u8;
+
+    /// Reads persistent storage into `dest`, returns the number of bytes read
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32;
+
+    /// Writes `src` to persistent storage, returns the number of bytes written
+    fn disk_write(&mut self, src: &[u8]) -> u32;
+
+    /// Prints a message to the debug console
+    fn trace(&mut self, text: &str);
+}
+
+/// The real console, a thin wrapper over the WASM-4 imports
+#[cfg(target_arch = "wasm32")]
+pub struct Wasm4;
+
+#[cfg(target_arch = "wasm32")]
+impl Platform for Wasm4 {
+    fn set_palette(&mut self, palette: [u32; 4]) {
+        unsafe { *crate::wasm4::PALETTE = palette }
+    }
+
+    fn set_draw_color(&mut self, idx: u16) {
+        unsafe { *crate::wasm4::DRAW_COLORS = idx }
+    }
+
+    fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        crate::wasm4::line(x1, y1, x2, y2);
+    }
+
+    fn rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        crate::wasm4::rect(x, y, width, height);
+    }
+
+    fn oval(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        crate::wasm4::oval(x, y, width, height);
+    }
+
+    fn hline(&mut self, x: i32, y: i32, len: u32) {
+        crate::wasm4::hline(x, y, len);
+    }
+
+    fn vline(&mut self, x: i32, y: i32, len: u32) {
+        crate::wasm4::vline(x, y, len);
+    }
+
+    fn text(&mut self, text: &str, x: i32, y: i32) {
+        crate::wasm4::text(text, x, y);
+    }
+
+    fn tone(&mut self, frequency: u32, duration: u32, volume: u32, flags: u32) {
+        crate::wasm4::tone(frequency, duration, volume, flags);
+    }
+
+    fn gamepad(&self) -> u8 {
+        unsafe { *crate::wasm4::GAMEPAD1 }
+    }
+
+    fn disk_read(&mut self, dest: &mut [u8]) -> u32 {
+        unsafe { crate::wasm4::diskr(dest.as_mut_ptr(), dest.len() as u32) }
+    }
+
+    fn disk_write(&mut self, src: &[u8]) -> u32 {
+        unsafe { crate::wasm4::diskw(src.as_ptr(), src.len() as u32) }
+    }
+
+    fn trace(&mut self, text: &str) {
+        crate::wasm4::trace(text);
+    }
+}
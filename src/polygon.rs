@@ -1,7 +1,6 @@
-use crate::gfx;
-use crate::ship::Ship;
-use crate::surface::Surface;
-use crate::wasm4;
+use crate::fixed::{Fixed, FixedPoint};
+use crate::platform::Platform;
+use crate::surface::{Hit, Surface};
 
 #[derive(Copy, Clone, Debug)]
 pub struct Point {
@@ -9,9 +8,10 @@ pub struct Point {
     pub y: f64,
 }
 
-#[derive(Debug)]
+/// Outline used for the ship's hull, in fixed point so collisions are exact
+#[derive(Clone, Debug, Default)]
 pub struct Polygon {
-    pub points: Vec<Point>,
+    pub points: Vec<FixedPoint>,
 }
 
 impl Point {
@@ -26,22 +26,23 @@ impl Polygon {
     }
 
     pub fn add_point(&mut self, x: f64, y: f64) {
-        self.points.push(Point { x, y });
+        self.points
+            .push(FixedPoint::new(Fixed::from_f64(x), Fixed::from_f64(y)));
     }
 
-    pub fn draw(&self, color: u16) {
-        gfx::set_draw_color(color);
+    pub fn draw<P: Platform>(&self, p: &mut P, color: u16) {
+        p.set_draw_color(color);
 
         let mut i = 0;
         while i < self.points.len() {
             let p1 = self.points[i];
             let p2 = self.points[(i + 1) % self.points.len()];
-            wasm4::line(p1.x as i32, p1.y as i32, p2.x as i32, p2.y as i32);
+            p.line(p1.x.floor(), p1.y.floor(), p2.x.floor(), p2.y.floor());
             i += 1;
         }
     }
 
-    pub fn translate(&mut self, x: f64, y: f64) {
+    pub fn translate(&mut self, x: Fixed, y: Fixed) {
         let mut i = 0;
         while i < self.points.len() {
             self.points[i].x += x;
@@ -50,17 +51,18 @@ impl Polygon {
         }
     }
 
-    pub fn rotate(&mut self, angle: f64) {
+    pub fn rotate(&mut self, angle: Fixed) {
+        let (sin, cos) = (angle.sin(), angle.cos());
         let mut i = 0;
         while i < self.points.len() {
             let p = self.points[i];
-            self.points[i].x = p.x * angle.cos() - p.y * angle.sin();
-            self.points[i].y = p.x * angle.sin() + p.y * angle.cos();
+            self.points[i].x = p.x * cos - p.y * sin;
+            self.points[i].y = p.x * sin + p.y * cos;
             i += 1;
         }
     }
 
-    pub fn scale(&mut self, scale: f64) {
+    pub fn scale(&mut self, scale: Fixed) {
         let mut i = 0;
         while i < self.points.len() {
             self.points[i].x *= scale;
@@ -69,24 +71,22 @@ impl Polygon {
         }
     }
 
-    pub fn check_collision(&self, surface: &Surface, ship: &Ship) -> u8 {
+    /// Deepest point where any edge of the polygon cuts into the ground
+    pub fn check_collision(&self, surface: &Surface) -> Option<Hit> {
+        let mut deepest: Option<Hit> = None;
+
         let mut i = 0;
         while i < self.points.len() {
-            let p = self.points[i];
-            let c = surface.check_collision(p.x, p.y, ship);
-            if c > 0 {
-                return c;
+            let p1 = self.points[i];
+            let p2 = self.points[(i + 1) % self.points.len()];
+            if let Some(hit) = surface.segment_hit(p1, p2) {
+                if deepest.is_none_or(|d| hit.depth > d.depth) {
+                    deepest = Some(hit);
+                }
             }
             i += 1;
         }
-        0
-    }
 
-    pub fn clone(&self) -> Polygon {
-        let mut new_poly = Polygon::new();
-        for point in self.points.iter() {
-            new_poly.add_point(point.x, point.y);
-        }
-        new_poly
+        deepest
     }
 }
@@ -0,0 +1,235 @@
+// Recording of a single run, the terrain seed, level & flight options plus the gamepad
+// state for every frame of flight. Feeding the same input back into a game started on the same seed
+// and level reproduces the run exactly
+
+const MAGIC: [u8; 3] = *b"WLR";
+const VERSION: u8 = 3;
+const HEADER_SZ: usize = 15;
+// Bits of the header's flags byte
+const FLAG_ASSIST: u8 = 1;
+// Most (gamepad, run length) pairs a recording holds, set aside up front so recording
+// never grows the heap mid flight. Runs only break when the input changes, so this is
+// minutes of real flying, anything past it isn't recorded. It keeps the hex text under
+// 4 KiB too, which is as much as the cart's heap can spare for it
+const MAX_RUNS: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub seed: u32,
+    pub level: u32,
+    /// Whether the ship's stability assist was on, it changes how the ship flies
+    pub stability_assist: bool,
+    // Input run length encoded as it's recorded, (gamepad, frames held) pairs
+    runs: Vec<(u8, u8)>,
+    frames: usize,
+}
+
+impl Replay {
+    pub fn new(seed: u32, level: u32) -> Self {
+        Self {
+            seed,
+            level,
+            stability_assist: true,
+            runs: Vec::with_capacity(MAX_RUNS),
+            frames: 0,
+        }
+    }
+
+    /// Add a frame of input, ignored once the recording is full
+    pub fn record(&mut self, gamepad: u8) {
+        // Growing the last run after a change was dropped would put the rest out of step
+        if self.is_full() {
+            return;
+        }
+        if let Some((value, run)) = self.runs.last_mut() {
+            if *value == gamepad && *run < u8::MAX {
+                *run += 1;
+                self.frames += 1;
+                return;
+            }
+        }
+        self.runs.push((gamepad, 1));
+        self.frames += 1;
+    }
+
+    /// Gamepad state for a frame, no buttons are held once the recording runs out
+    pub fn get(&self, frame: usize) -> u8 {
+        let mut start = 0;
+        for &(value, run) in self.runs.iter() {
+            start += run as usize;
+            if frame < start {
+                return value;
+            }
+        }
+        0
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// No room for a change of input, the rest of the run won't be recorded
+    pub fn is_full(&self) -> bool {
+        self.runs.len() >= MAX_RUNS
+    }
+
+    /// Serialise as a small header followed by (gamepad, run length) pairs
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.encoded_len());
+        out.extend(self.bytes());
+        out
+    }
+
+    fn encoded_len(&self) -> usize {
+        HEADER_SZ + self.runs.len() * 2
+    }
+
+    fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        let mut header = [0u8; HEADER_SZ];
+        header[0..3].copy_from_slice(&MAGIC);
+        header[3] = VERSION;
+        header[4..8].copy_from_slice(&self.seed.to_le_bytes());
+        header[8..10].copy_from_slice(&(self.level as u16).to_le_bytes());
+        header[10..14].copy_from_slice(&(self.frames as u32).to_le_bytes());
+        if self.stability_assist {
+            header[14] |= FLAG_ASSIST;
+        }
+
+        header
+            .into_iter()
+            .chain(self.runs.iter().flat_map(|&(value, run)| [value, run]))
+    }
+
+    pub fn decode(data: &[u8]) -> Option<Replay> {
+        if data.len() < HEADER_SZ || data[0..3] != MAGIC || data[3] != VERSION {
+            return None;
+        }
+
+        let seed = u32::from_le_bytes(data[4..8].try_into().ok()?);
+        let level = u16::from_le_bytes(data[8..10].try_into().ok()?) as u32;
+        let count = u32::from_le_bytes(data[10..14].try_into().ok()?) as usize;
+        let stability_assist = data[14] & FLAG_ASSIST != 0;
+
+        let data = &data[HEADER_SZ..];
+        if !data.len().is_multiple_of(2) || data.len() / 2 > MAX_RUNS {
+            return None;
+        }
+        let runs: Vec<(u8, u8)> = data.chunks(2).map(|run| (run[0], run[1])).collect();
+        let frames = runs.iter().map(|&(_, run)| run as usize).sum();
+        if frames != count {
+            return None;
+        }
+
+        Some(Replay {
+            seed,
+            level,
+            stability_assist,
+            runs,
+            frames,
+        })
+    }
+
+    /// Encoded replay as hex text, so it can be copied out of the debug console
+    pub fn to_hex(&self) -> String {
+        // Straight into a string of the right size, a full recording is most of the heap
+        let mut hex = String::with_capacity(self.encoded_len() * 2);
+        for b in self.bytes() {
+            hex.push(char::from_digit((b >> 4) as u32, 16).unwrap_or('0'));
+            hex.push(char::from_digit((b & 0xf) as u32, 16).unwrap_or('0'));
+        }
+        hex
+    }
+
+    pub fn from_hex(text: &str) -> Option<Replay> {
+        let text = text.trim();
+        if !text.len().is_multiple_of(2) || !text.is_ascii() {
+            return None;
+        }
+        let mut data = Vec::new();
+        for i in (0..text.len()).step_by(2) {
+            data.push(u8::from_str_radix(&text[i..i + 2], 16).ok()?);
+        }
+        Replay::decode(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded(input: &[u8]) -> Replay {
+        let mut replay = Replay::new(1234, 3);
+        for &gamepad in input {
+            replay.record(gamepad);
+        }
+        replay
+    }
+
+    #[test]
+    fn records_runs() {
+        let mut input = vec![0; 300];
+        input.extend([1, 1, 0x20, 0]);
+        let replay = recorded(&input);
+        assert_eq!(replay.len(), input.len());
+        for (frame, &gamepad) in input.iter().enumerate() {
+            assert_eq!(replay.get(frame), gamepad);
+        }
+        assert_eq!(replay.get(input.len()), 0);
+        // 300 frames of nothing need two runs, then one per change
+        assert_eq!(replay.encode().len(), HEADER_SZ + 5 * 2);
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut replay = recorded(&[0, 0, 1, 1, 1, 0x10, 0x41, 0]);
+        replay.stability_assist = false;
+        assert_eq!(Replay::decode(&replay.encode()), Some(replay.clone()));
+        assert_eq!(Replay::from_hex(&replay.to_hex()), Some(replay.clone()));
+        assert_eq!(Replay::from_hex(&(replay.to_hex() + "\n")), Some(replay));
+    }
+
+    #[test]
+    fn stops_recording_when_full() {
+        let input: Vec<u8> = (0..MAX_RUNS + 10).map(|i| (i % 2) as u8).collect();
+        let mut replay = recorded(&input);
+        assert!(replay.is_full());
+        assert_eq!(replay.len(), MAX_RUNS);
+        // Nothing more is taken, even input that would only lengthen the last run
+        replay.record(((MAX_RUNS - 1) % 2) as u8);
+        assert_eq!(replay.len(), MAX_RUNS);
+        assert_eq!(Replay::decode(&replay.encode()), Some(replay.clone()));
+        assert!(replay.to_hex().len() < 4096);
+    }
+
+    #[test]
+    fn odd_run_bytes_are_rejected() {
+        let mut data = recorded(&[1, 1, 0]).encode();
+        data.push(1);
+        assert_eq!(Replay::decode(&data), None);
+    }
+
+    #[test]
+    fn wrong_frame_count_is_rejected() {
+        let mut data = recorded(&[1, 1, 0]).encode();
+        data[10] += 1;
+        assert_eq!(Replay::decode(&data), None);
+    }
+
+    #[test]
+    fn bad_header_or_hex_is_rejected() {
+        let data = recorded(&[1]).encode();
+        assert_eq!(Replay::decode(&data[..HEADER_SZ - 1]), None);
+
+        let mut wrong_version = data.clone();
+        wrong_version[3] += 1;
+        assert_eq!(Replay::decode(&wrong_version), None);
+
+        let hex = recorded(&[1]).to_hex();
+        assert_eq!(Replay::from_hex(&hex[1..]), None);
+        assert_eq!(Replay::from_hex(&hex.replace('5', "g")), None);
+    }
+}
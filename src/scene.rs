@@ -0,0 +1,44 @@
+// The screens the game can be on. Each scene has enter/update/draw/exit hooks in
+// Game, and the rules below say which scene can follow which, so adding a screen
+// means a new variant, its transitions and its hooks, the flight loop is untouched
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Scene {
+    Title,
+    LevelIntro,
+    Playing,
+    Paused,
+    Landed,
+    Crashed,
+    GameOver,
+    EnterInitials,
+    HighScores,
+    Options,
+}
+
+impl Scene {
+    pub fn can_change_to(self, next: Scene) -> bool {
+        use Scene::*;
+
+        matches!(
+            (self, next),
+            (Title, LevelIntro)
+                | (Title, HighScores)
+                | (Title, Options)
+                | (LevelIntro, Playing)
+                | (Playing, Paused)
+                | (Playing, Landed)
+                | (Playing, Crashed)
+                | (Playing, GameOver)
+                | (Paused, Playing)
+                | (Paused, Title)
+                | (Landed, LevelIntro)
+                | (Crashed, LevelIntro)
+                | (GameOver, EnterInitials)
+                | (GameOver, Title)
+                | (EnterInitials, HighScores)
+                | (HighScores, Title)
+                | (Options, Title)
+        )
+    }
+}
@@ -0,0 +1,209 @@
+// Top 10 high score table, kept in the console's persistent storage (1 KiB max)
+
+use crate::platform::Platform;
+
+const MAGIC: [u8; 4] = *b"WLHS";
+const VERSION: u8 = 1;
+const MAX_ENTRIES: usize = 10;
+const ENTRY_SZ: usize = 17;
+const HEADER_SZ: usize = 6;
+const SAVE_SZ: usize = HEADER_SZ + MAX_ENTRIES * ENTRY_SZ + 2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub initials: [u8; 3],
+    pub score: u32,
+    /// Terrain seed the run started on
+    pub seed: u32,
+    pub landings: u16,
+    /// Number of frames spent in flight over the whole run
+    pub frames: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HighScores {
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Read the table from disk, anything missing or corrupt gives an empty table
+    pub fn load<P: Platform>(p: &mut P) -> Self {
+        let mut buf = [0u8; SAVE_SZ];
+        let n = p.disk_read(&mut buf) as usize;
+        HighScores::decode(&buf[..n]).unwrap_or_default()
+    }
+
+    pub fn save<P: Platform>(&self, p: &mut P) {
+        p.disk_write(&self.encode());
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Would this score make it onto the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_ENTRIES || self.entries.iter().any(|e| score > e.score))
+    }
+
+    /// Add an entry keeping the table sorted, returns its position or None if it didn't make the cut
+    pub fn insert(&mut self, entry: Entry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+
+        // Ties go below existing entries, first to get the score keeps the spot
+        let pos = self
+            .entries
+            .iter()
+            .position(|e| entry.score > e.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(pos, entry);
+        self.entries.truncate(MAX_ENTRIES);
+
+        Some(pos)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::from(MAGIC);
+        out.push(VERSION);
+        out.push(self.entries.len() as u8);
+
+        for e in self.entries.iter() {
+            out.extend_from_slice(&e.initials);
+            out.extend_from_slice(&e.score.to_le_bytes());
+            out.extend_from_slice(&e.seed.to_le_bytes());
+            out.extend_from_slice(&e.landings.to_le_bytes());
+            out.extend_from_slice(&e.frames.to_le_bytes());
+        }
+
+        let sum = checksum(&out);
+        out.extend_from_slice(&sum.to_le_bytes());
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> Option<HighScores> {
+        if data.len() < HEADER_SZ + 2 || data[0..4] != MAGIC || data[4] != VERSION {
+            return None;
+        }
+
+        let count = data[5] as usize;
+        let end = HEADER_SZ + count * ENTRY_SZ;
+        if count > MAX_ENTRIES || data.len() < end + 2 {
+            return None;
+        }
+        if checksum(&data[..end]) != u16::from_le_bytes([data[end], data[end + 1]]) {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        for e in data[HEADER_SZ..end].chunks(ENTRY_SZ) {
+            entries.push(Entry {
+                initials: [e[0], e[1], e[2]],
+                score: u32::from_le_bytes([e[3], e[4], e[5], e[6]]),
+                seed: u32::from_le_bytes([e[7], e[8], e[9], e[10]]),
+                landings: u16::from_le_bytes([e[11], e[12]]),
+                frames: u32::from_le_bytes([e[13], e[14], e[15], e[16]]),
+            });
+        }
+
+        Some(HighScores { entries })
+    }
+}
+
+// Fletcher-16, enough to spot a truncated or scribbled save
+fn checksum(data: &[u8]) -> u16 {
+    let mut a = 0u16;
+    let mut b = 0u16;
+    for &byte in data.iter() {
+        a = (a + byte as u16) % 255;
+        b = (b + a) % 255;
+    }
+    (b << 8) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headless::Headless;
+
+    fn entry(initials: &[u8; 3], score: u32) -> Entry {
+        Entry {
+            initials: *initials,
+            score,
+            seed: score * 7,
+            landings: 3,
+            frames: 4000,
+        }
+    }
+
+    fn table(scores: &[u32]) -> HighScores {
+        let mut table = HighScores::new();
+        for &score in scores {
+            table.insert(entry(b"ABC", score));
+        }
+        table
+    }
+
+    #[test]
+    fn round_trips() {
+        for table in [HighScores::new(), table(&[500, 200, 900])] {
+            assert_eq!(HighScores::decode(&table.encode()), Some(table));
+        }
+    }
+
+    #[test]
+    fn round_trips_through_the_disk() {
+        let mut p = Headless::new();
+        let table = table(&[10, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
+        table.save(&mut p);
+        assert!(p.disk.len() <= SAVE_SZ);
+        assert_eq!(HighScores::load(&mut p), table);
+    }
+
+    #[test]
+    fn bad_checksum_is_rejected() {
+        let mut data = table(&[500, 200]).encode();
+        data[HEADER_SZ + 3] ^= 1;
+        assert_eq!(HighScores::decode(&data), None);
+    }
+
+    #[test]
+    fn truncated_or_unknown_saves_are_rejected() {
+        let data = table(&[500, 200]).encode();
+        assert_eq!(HighScores::decode(&data[..data.len() - 1]), None);
+        assert_eq!(HighScores::decode(&[]), None);
+
+        let mut wrong_version = data.clone();
+        wrong_version[4] += 1;
+        assert_eq!(HighScores::decode(&wrong_version), None);
+    }
+
+    #[test]
+    fn ties_go_below_existing_entries() {
+        let mut table = HighScores::new();
+        table.insert(entry(b"AAA", 300));
+        assert_eq!(table.insert(entry(b"BBB", 300)), Some(1));
+        assert_eq!(table.insert(entry(b"CCC", 400)), Some(0));
+        let initials: Vec<_> = table.entries().iter().map(|e| e.initials).collect();
+        assert_eq!(initials, [*b"CCC", *b"AAA", *b"BBB"]);
+    }
+
+    #[test]
+    fn keeps_the_top_ten() {
+        let mut table = table(&[10, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
+        assert!(!table.qualifies(10));
+        assert_eq!(table.insert(entry(b"LOW", 10)), None);
+        assert_eq!(table.insert(entry(b"TOP", 15)), Some(9));
+        assert_eq!(table.entries().len(), MAX_ENTRIES);
+        assert_eq!(table.entries().last().map(|e| e.score), Some(15));
+        assert_eq!(table.insert(entry(b"NIL", 0)), None);
+    }
+}
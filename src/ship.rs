@@ -1,25 +1,95 @@
+use crate::camera::Camera;
+use crate::contact::{Contact, CrashKind};
+use crate::fixed::{Fixed, FixedPoint};
+use crate::level::{Level, Pad};
 use crate::particle::Particle;
+use crate::platform::Platform;
 use crate::polygon;
 use crate::polygon::Point;
 use crate::rand_tab;
-use crate::surface::Surface;
-use crate::wasm4;
+use crate::surface::{Hit, Surface};
 
-const SHIP_Y: f64 = 50.0;
+// Furthest the ship moves on screen before its path is checked in steps, in pixels
+const SWEEP_STEP: Fixed = Fixed::from_int(2);
+const MAX_SWEEP_STEPS: usize = 16;
+// Frames both legs must be down before a landing counts
+const SETTLE_TIME: u32 = 30;
+// Past 45 degrees off vertical a settling ship has fallen over
+const TIP_ANGLE: Fixed = Fixed::from_f64(std::f64::consts::FRAC_PI_4);
+// Share of the ship's sideways speed it keeps bouncing back off the edge of the world
+const EDGE_BOUNCE: Fixed = Fixed::from_f64(0.5);
+// Index of the foot in each leg polygon
+const FOOT: usize = 1;
+// Angular acceleration from the RCS thrusters, in radians per frame, & their fuel use
+const RCS_THRUST: Fixed = Fixed::from_f64(0.001);
+const RCS_FUEL: Fixed = Fixed::from_f64(0.03);
+
+/// Tunable engine & mass figures, the ship gets livelier as the fuel burns off
+#[derive(Copy, Clone, Debug)]
+pub struct ShipSpec {
+    /// Mass of the empty ship
+    pub dry_mass: f64,
+    /// Mass of each unit of fuel on the HUD
+    pub fuel_mass: f64,
+    /// Main engine force, acceleration is this over the total mass
+    pub thrust: f64,
+    /// Specific impulse as an exhaust velocity in pixels per frame, higher burns less
+    /// fuel for the same thrust
+    pub isp: f64,
+}
+
+impl ShipSpec {
+    pub fn new() -> Self {
+        // Full tank on the first level accelerates at 0.002 a frame, burning 0.2 fuel
+        Self {
+            dry_mass: 1.0,
+            fuel_mass: 0.002,
+            thrust: 0.003,
+            isp: 7.5,
+        }
+    }
+
+    /// Fuel units burnt each frame the engine is on
+    pub fn burn_rate(&self) -> f64 {
+        self.thrust / self.isp / self.fuel_mass
+    }
+}
+
+impl Default for ShipSpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 pub struct Ship {
-    pub destroyed: bool,
-    pub crash_reason: String,
-    pub landed: bool,
-    pub angle: f64,
-    pub scale: f64,
+    /// How the flight ended, if it has
+    pub contact: Contact,
+    /// Where the hull touched the ground, when it has
+    pub hit: Option<Hit>,
+    /// How far through the frame the hull hit the ground, 0 to 1
+    pub impact_time: Option<Fixed>,
+    /// Which legs are on the pad while settling
+    legs_down: [bool; 2],
+    settle_frames: u32,
+    angular_velocity: Fixed,
+    // RCS thrusters firing, 1 to turn clockwise, -1 anticlockwise
+    rcs: i8,
+    /// Fire the RCS against any spin when not turning
+    pub stability_assist: bool,
+    pub angle: Fixed,
+    /// View the ship is seen through, it's on screen for collisions as well as drawing
+    pub camera: Camera,
+    pub spec: ShipSpec,
 
     parts: Vec<polygon::Polygon>,
-    pub pos: polygon::Point,
-    velocity: polygon::Point,
-    thrust: f64,
+    pub pos: FixedPoint,
+    velocity: FixedPoint,
     engine_on: bool,
-    fuel: f64,
+    // Share of full thrust the engine gives when on, 0 to 1
+    throttle: Fixed,
+    // Builds up by the throttle each frame, a flame particle comes out every whole one
+    flame: f64,
+    fuel: Fixed,
     particles: [Particle; 30],
 }
 
@@ -44,140 +114,492 @@ impl Ship {
         leg2.add_point(-6.0, -5.0);
         leg2.add_point(-3.0, -3.0);
 
-        let mut parts_vec = Vec::new();
-        parts_vec.push(body);
-        parts_vec.push(leg1);
-        parts_vec.push(leg2);
-        parts_vec.push(flame);
-
         Self {
-            parts: parts_vec,
-            pos: Point::new(200.0, 15.0),
-            velocity: Point::new(0.29, 0.0),
-            scale: 1.0,
-            thrust: 0.002,
+            parts: vec![body, leg1, leg2, flame],
+            pos: FixedPoint::new(Fixed::from_int(200), Fixed::from_int(15)),
+            velocity: FixedPoint::new(Fixed::from_f64(0.29), Fixed::ZERO),
+            camera: Camera::new(),
+            spec: ShipSpec::new(),
             engine_on: false,
-            angle: 0.0,
-            fuel: 250.0,
-            destroyed: false,
-            crash_reason: String::new(),
-            landed: false,
+            throttle: Fixed::ONE,
+            flame: 0.0,
+            angle: Fixed::ZERO,
+            fuel: Fixed::from_int(250),
+            contact: Contact::None,
+            hit: None,
+            impact_time: None,
+            legs_down: [false; 2],
+            settle_frames: 0,
+            angular_velocity: Fixed::ZERO,
+            rcs: 0,
+            stability_assist: true,
             particles: [Particle::new(0.0, 0.0, 0.0, 0.0, 0.0); 30],
         }
     }
 
-    pub fn update(&mut self, gravity: f64) {
+    pub fn for_level(level: &Level) -> Self {
+        let mut ship = Ship::new();
+        ship.fuel = Fixed::from_f64(level.fuel);
+        ship.pos = FixedPoint::from_point(level.start_pos);
+        ship.velocity = FixedPoint::from_point(level.start_velocity);
+        ship
+    }
+
+    /// Move the ship on a frame, drag is the share of its velocity through the air that
+    /// the air takes off, & wind is the air's speed across the screen
+    pub fn update(&mut self, gravity: f64, drag: f64, wind: Fixed, surface: &Surface) {
+        let gravity = Fixed::from_f64(gravity);
+        let drag = Fixed::from_f64(drag);
+        if let Contact::Settling { pad, score_quality } = self.contact {
+            self.engine_on = false;
+            self.rcs = 0;
+            self.settle(gravity, pad, score_quality);
+        } else {
+            self.fly(gravity, drag, wind, surface);
+        }
+
+        // Update particles
+        for particle in self.particles.iter_mut() {
+            particle.update();
+        }
+    }
+
+    fn fly(&mut self, gravity: Fixed, drag: Fixed, wind: Fixed, surface: &Surface) {
+        // Anything the sweep finds this frame only counts if collide then sets the contact
+        self.hit = None;
+        self.impact_time = None;
+
+        // Turning needs the RCS to start the ship spinning, and to stop it again
+        if self.rcs != 0 {
+            self.angular_velocity += RCS_THRUST * Fixed::from_int(self.rcs as i32);
+            self.burn_rcs(Fixed::ONE);
+            self.puff(self.rcs);
+        } else if self.stability_assist
+            && self.angular_velocity != Fixed::ZERO
+            && self.fuel > Fixed::ZERO
+        {
+            let dir = -self.angular_velocity.signum();
+            let burn = self.angular_velocity.abs().min(RCS_THRUST);
+            self.angular_velocity += burn * Fixed::from_int(dir);
+            self.burn_rcs(burn / RCS_THRUST);
+            self.puff(dir as i8);
+        }
+        self.angle += self.angular_velocity;
+
         if self.engine_on {
-            self.velocity.x += self.thrust * self.angle.cos();
-            self.velocity.y += self.thrust * self.angle.sin();
-            self.fuel -= 0.2;
-            wasm4::tone((self.fuel + 80.0) as u32, 4, 20, wasm4::TONE_NOISE);
+            let accel = Fixed::from_f64(self.spec.thrust) * self.throttle / self.mass();
+            self.velocity.x += accel * self.angle.cos();
+            self.velocity.y += accel * self.angle.sin();
+            self.fuel -= Fixed::from_f64(self.spec.burn_rate()) * self.throttle;
         }
 
         self.velocity.y += gravity;
+        self.velocity.x -= (self.velocity.x - wind) * drag;
+        self.velocity.y -= self.velocity.y * drag;
         self.pos.x += self.velocity.x;
         self.pos.y += self.velocity.y;
 
-        if self.pos.y < 10.0 {
-            self.destroyed = true;
-            self.crash_reason = String::from("You zoomed off\ninto space!");
+        let (left, right) = surface.bounds();
+        if self.pos.x < left || self.pos.x > right {
+            self.pos.x = self.pos.x.clamp(left, right);
+            self.velocity.x = -self.velocity.x * EDGE_BOUNCE;
+        }
+        self.sweep(surface);
+
+        if self.pos.y < Fixed::from_int(10) {
+            self.contact = Contact::Crashed {
+                kind: CrashKind::Space,
+            };
+        }
+
+        // Thruster effects, fewer & shorter lived particles when throttled back
+        if self.engine_on {
+            self.flame += self.throttle.to_f64();
+        }
+        if self.flame >= 1.0 {
+            self.flame -= 1.0;
+            let mut flame_point = self.parts.get(3).unwrap().clone();
+            flame_point.scale(self.camera.zoom);
+            flame_point.rotate(self.angle);
+            flame_point.translate(self.camera.ship.x, self.camera.ship.y);
+
+            let at = self
+                .camera
+                .to_world(flame_point.points[0].to_point(), self.pos);
+            let particle = Particle::new(
+                at.x,
+                at.y,
+                (self.angle.to_f64() - std::f64::consts::PI) + ((rand_tab::f64() - 0.5) * 0.6),
+                1.2 + rand_tab::f64(),
+                (6.0 + rand_tab::f64() * 5.0) * self.camera.zoom.to_f64() * self.throttle.to_f64(),
+            );
+            self.spawn(particle);
+        }
+    }
+
+    fn burn_rcs(&mut self, amount: Fixed) {
+        self.fuel = (self.fuel - RCS_FUEL * amount).max(Fixed::ZERO);
+    }
+
+    // Puff of gas from the side of the nose, the RCS thruster turning the ship one way
+    fn puff(&mut self, dir: i8) {
+        let side = -dir as f64;
+        let mut nozzle = polygon::Polygon::new();
+        nozzle.add_point(4.0, 2.5 * side);
+        nozzle.scale(self.camera.zoom);
+        nozzle.rotate(self.angle);
+        nozzle.translate(self.camera.ship.x, self.camera.ship.y);
+
+        let at = self.camera.to_world(nozzle.points[0].to_point(), self.pos);
+        let particle = Particle::new(
+            at.x,
+            at.y,
+            self.angle.to_f64()
+                + side * std::f64::consts::FRAC_PI_2
+                + (rand_tab::f64() - 0.5) * 0.4,
+            0.8,
+            3.0 * self.camera.zoom.to_f64(),
+        );
+        self.spawn(particle);
+    }
+
+    // find a dead particle and replace it
+    fn spawn(&mut self, particle: Particle) {
+        if let Some(dead) = self.particles.iter_mut().find(|p| p.is_dead()) {
+            *dead = particle;
+        }
+    }
+
+    // Rock on the pad until both feet are down, pivoting on the foot that's down with
+    // gravity pulling the body round
+    fn settle(&mut self, gravity: Fixed, pad: Pad, score_quality: u32) {
+        if self.legs_down == [true; 2] {
+            self.settle_frames += 1;
+            if self.settle_frames >= SETTLE_TIME {
+                self.contact = Contact::Landed { pad, score_quality };
+            }
+            return;
+        }
+
+        let foot = self.foot(if self.legs_down[0] { 0 } else { 1 });
+        let r = FixedPoint::new(self.camera.ship.x - foot.x, self.camera.ship.y - foot.y);
+        self.angular_velocity += r.x * gravity / (r.x * r.x + r.y * r.y);
+        self.pivot(foot, self.angular_velocity);
+    }
+
+    // Turn the ship about a point on screen, moving it so that point stays put
+    fn pivot(&mut self, about: FixedPoint, by: Fixed) {
+        let r = FixedPoint::new(self.camera.ship.x - about.x, self.camera.ship.y - about.y);
+        let moved = FixedPoint::new(
+            r.x * by.cos() - r.y * by.sin() - r.x,
+            r.x * by.sin() + r.y * by.cos() - r.y,
+        );
+
+        self.angle += by;
+        // Sideways movement on screen is scaled by the view, vertical isn't
+        self.pos.x += moved.x / self.camera.zoom;
+        self.pos.y += moved.y;
+    }
+
+    fn foot(&self, leg: usize) -> FixedPoint {
+        self.part_poly(leg + 1).points[FOOT]
+    }
+
+    // A fast ship can skip clean over a spike or a pad edge between frames, so step along
+    // its path and stop it where the hull first touches the ground. The end of the path
+    // is left to collide, once the ground has been worked out for the new view
+    fn sweep(&mut self, surface: &Surface) {
+        // The view scales sideways movement but not vertical
+        let dx = self.velocity.x * self.camera.zoom;
+        let moved = (dx * dx + self.velocity.y * self.velocity.y).sqrt();
+        if moved <= SWEEP_STEP {
+            return;
+        }
+
+        let steps = ((moved / SWEEP_STEP).ceil() as usize).min(MAX_SWEEP_STEPS);
+        let end = self.pos;
+        for i in 1..steps {
+            let t = Fixed::from_int(i as i32) / Fixed::from_int(steps as i32);
+            let pos = FixedPoint::new(
+                end.x - self.velocity.x * (Fixed::ONE - t),
+                end.y - self.velocity.y * (Fixed::ONE - t),
+            );
+
+            let view = self.camera.view_at(pos);
+            let hit = (0..3).any(|part| {
+                surface
+                    .hit_at(&self.part_poly(part), view.x, view.y)
+                    .is_some()
+            });
+            if hit {
+                self.pos = pos;
+                self.impact_time = Some(t);
+                return;
+            }
         }
     }
 
     pub fn set_engines(&mut self, state: bool) {
         self.engine_on = state;
-        if self.fuel <= 0.0 {
+        if self.fuel <= Fixed::ZERO {
             self.engine_on = false;
-            self.fuel = 0.0;
+            self.fuel = Fixed::ZERO;
         }
     }
 
+    /// Set the engine throttle, from 0 for idle to 1 for full thrust
+    pub fn set_throttle(&mut self, throttle: f64) {
+        self.throttle = Fixed::from_f64(throttle.clamp(0.0, 1.0));
+    }
+
+    pub fn get_throttle(&self) -> f64 {
+        self.throttle.to_f64()
+    }
+
+    /// Fire the RCS to turn, 1 for clockwise, -1 anticlockwise or 0 to stop firing
+    pub fn set_rcs(&mut self, dir: i8) {
+        self.rcs = if self.fuel > Fixed::ZERO {
+            dir.signum()
+        } else {
+            0
+        };
+    }
+
+    pub fn get_angular_velocity(&self) -> f64 {
+        self.angular_velocity.to_f64()
+    }
+
+    pub fn is_thrusting(&self) -> bool {
+        self.engine_on
+    }
+
     pub fn get_fuel(&self) -> f64 {
-        self.fuel
+        self.fuel.to_f64()
+    }
+
+    /// Dry mass plus whatever fuel is left
+    pub fn get_mass(&self) -> f64 {
+        self.mass().to_f64()
+    }
+
+    fn mass(&self) -> Fixed {
+        Fixed::from_f64(self.spec.dry_mass) + self.fuel * Fixed::from_f64(self.spec.fuel_mass)
     }
 
     pub fn get_pos(&self) -> Point {
-        self.pos
+        self.pos.to_point()
     }
 
     pub fn get_velocity(&self) -> Point {
-        self.velocity
+        self.velocity.to_point()
     }
 
     pub fn get_speed(&self) -> f64 {
         let mag = self.velocity.x * self.velocity.x + self.velocity.y * self.velocity.y;
-        mag.sqrt()
+        mag.sqrt().to_f64()
     }
 
-    pub fn draw(&mut self, surface: &Surface) {
-        // Draw the main parts of the ship
-        self.draw_part(0, surface, 0x3);
-        self.draw_part(1, surface, 0x2);
-        self.draw_part(2, surface, 0x2);
-
-        // Thruster effects
-        if self.engine_on {
-            let mut flame_point = self.parts.get(3).unwrap().clone();
-            flame_point.scale(self.scale);
-            flame_point.rotate(self.angle);
-            flame_point.translate(80.0, SHIP_Y);
-
-            // find a dead particle and replace it
-            for particle in self.particles.iter_mut() {
-                if particle.is_dead() {
-                    *particle = Particle::new(
-                        flame_point.points[0].x,
-                        flame_point.points[0].y,
-                        (self.angle - 3.14) + ((rand_tab::f64() - 0.5) * 0.6),
-                        1.2 + rand_tab::f64(),
-                        (6.0 + rand_tab::f64() * 5.0) * self.scale,
-                    );
-                    break;
+    /// Check the hull against the ground from the surface's last update, setting the
+    /// contact. Needs no drawing, so it runs as part of the physics step
+    pub fn collide(&mut self, surface: &Surface) {
+        // The body & legs, the flame doesn't touch anything
+        let hits = [0, 1, 2].map(|part| self.part_poly(part).check_collision(surface));
+        let before = self.contact;
+        match self.contact {
+            Contact::None => self.touch_down(surface, hits),
+            Contact::Settling { .. } => {
+                // The body hitting the ground, or a leg coming down off the pad, means it fell
+                let off_pad = |hit: Option<Hit>| hit.is_some_and(|h| h.pad.is_none());
+                if hits[0].is_some() || off_pad(hits[1]) || off_pad(hits[2]) {
+                    self.tip_over();
+                } else {
+                    for leg in 0..2 {
+                        if hits[leg + 1].is_some() && !self.legs_down[leg] {
+                            self.legs_down[leg] = true;
+                            self.angular_velocity = Fixed::ZERO;
+                        }
+                    }
+                    if (self.angle + Fixed::FRAC_PI_2).abs() > TIP_ANGLE {
+                        self.tip_over();
+                    }
                 }
             }
+            _ => {}
         }
 
-        // Update and draw particles
-        for particle in self.particles.iter_mut() {
-            particle.update();
-            particle.draw(0x4);
+        // The hit is kept from the frame that set the contact, the deepest part of the hull
+        if self.contact != before {
+            if let Some(hit) = hits.iter().flatten().max_by_key(|h| h.depth) {
+                self.hit = Some(*hit);
+                self.impact_time = self.impact_time.or(Some(Fixed::ONE));
+            }
         }
     }
 
-    fn draw_part(&mut self, part: usize, surface: &Surface, color: u16) {
-        let mut p = self.parts.get(part).unwrap().clone();
+    // First touch of the ground, only the legs can land & the landing limits are judged
+    // now. A good touchdown starts the ship settling on whichever legs are down
+    fn touch_down(&mut self, surface: &Surface, hits: [Option<Hit>; 3]) {
+        // The body can't land, and neither can a leg that's missed the pad
+        let off_pad = |hit: Option<Hit>| hit.filter(|h| h.pad.is_none());
+        if hits[0].or(off_pad(hits[1])).or(off_pad(hits[2])).is_some() {
+            self.contact = Contact::Crashed {
+                kind: CrashKind::Terrain,
+            };
+            return;
+        }
 
-        p.scale(self.scale);
-        p.rotate(self.angle);
+        let Some(hit) = hits[1].or(hits[2]) else {
+            return;
+        };
+        self.contact = match surface.check_collision(&hit, self) {
+            Contact::Landed { pad, score_quality } => {
+                self.land_legs(hits);
+                Contact::Settling { pad, score_quality }
+            }
+            contact => contact,
+        };
+    }
 
-        // Draw poly in screen coordinates
-        p.translate(80.0, SHIP_Y);
+    fn land_legs(&mut self, hits: [Option<Hit>; 3]) {
+        self.legs_down = [hits[1].is_some(), hits[2].is_some()];
+        self.settle_frames = 0;
 
-        // Collision detection is done in screen coordinates now
-        let c = p.check_collision(surface, self);
-        if c >= 2 {
-            self.destroyed = true;
-            if c == 2 {
-                self.crash_reason = String::from("Crashed into the\nlunar surface");
-            }
-            if c == 3 {
-                self.crash_reason = String::from("Landed too fast!");
-            }
-            if c == 4 {
-                self.crash_reason = String::from("Landed at too\nsteep an angle");
-            }
-            if c == 5 {
-                self.crash_reason = String::from("Sheared landing\nlegs");
-            }
+        // With one foot down the ship's motion turns into spin about it
+        if self.legs_down == [true; 2] {
+            self.angular_velocity = Fixed::ZERO;
+        } else {
+            let foot = self.foot(if self.legs_down[0] { 0 } else { 1 });
+            let r = FixedPoint::new(self.camera.ship.x - foot.x, self.camera.ship.y - foot.y);
+            let v = FixedPoint::new(self.velocity.x * self.camera.zoom, self.velocity.y);
+            self.angular_velocity += (r.x * v.y - r.y * v.x) / (r.x * r.x + r.y * r.y);
+        }
+        self.velocity = FixedPoint::default();
+    }
 
-            return;
+    fn tip_over(&mut self) {
+        self.contact = Contact::Crashed {
+            kind: CrashKind::Tipped,
+        };
+    }
+
+    pub fn is_landed(&self) -> bool {
+        self.contact.is_landed()
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.contact.is_destroyed()
+    }
+
+    /// Touched down on the pad but not yet settled, the controls are hands off
+    pub fn is_settling(&self) -> bool {
+        matches!(self.contact, Contact::Settling { .. })
+    }
+
+    pub fn draw<P: Platform>(&self, p: &mut P) {
+        // Draw the main parts of the ship
+        self.part_poly(0).draw(p, 0x3);
+        self.part_poly(1).draw(p, 0x2);
+        self.part_poly(2).draw(p, 0x2);
+
+        for particle in self.particles.iter() {
+            particle.draw(p, &self.camera, 0x4);
         }
+    }
 
-        if c == 1 {
-            self.landed = true;
-            return;
+    // One part of the ship in screen coordinates
+    fn part_poly(&self, part: usize) -> polygon::Polygon {
+        let mut poly = self.parts.get(part).unwrap().clone();
+
+        poly.scale(self.camera.zoom);
+        poly.rotate(self.angle);
+        poly.translate(self.camera.ship.x, self.camera.ship.y);
+        poly
+    }
+}
+
+impl Default for Ship {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lower the ship a little at a time, leaning by degrees off vertical with no velocity,
+    // until it touches down on the middle of the first level's first pad
+    fn touch_down_leaning(lean: f64) -> (Level, Surface, Ship) {
+        let mut level = Level::new(1);
+        level.start_velocity = Point::new(0.0, 0.0);
+        level.max_angle = 60.0;
+        let mut surface = Surface::new(1234, &level);
+        let mut ship = Ship::for_level(&level);
+        ship.pos.x = Fixed::from_f64(surface.pad_centres().next().unwrap());
+        ship.angle = Fixed::from_f64((lean - 90.0).to_radians());
+        ship.camera.reset(ship.pos);
+
+        while ship.contact == Contact::None {
+            assert!(
+                ship.pos.y < Fixed::from_int(1000),
+                "never touched the ground"
+            );
+            ship.pos.y += Fixed::from_f64(0.125);
+            ship.camera.hold(ship.pos);
+            surface.update(&ship.camera);
+            ship.collide(&surface);
+        }
+        (level, surface, ship)
+    }
+
+    // A frame of the game's physics step while settling, the view holds still
+    fn step(level: &Level, surface: &mut Surface, ship: &mut Ship) {
+        ship.update(level.gravity, 0.0, Fixed::ZERO, surface);
+        ship.camera.hold(ship.pos);
+        surface.update(&ship.camera);
+        ship.collide(surface);
+    }
+
+    #[test]
+    fn one_leg_touchdown_settles_then_lands() {
+        let (level, mut surface, mut ship) = touch_down_leaning(10.0);
+        assert!(ship.is_settling(), "{:?}", ship.contact);
+        assert_eq!(ship.legs_down.iter().filter(|&&down| down).count(), 1);
+
+        for _ in 0..1000 {
+            if ship.legs_down == [true; 2] {
+                break;
+            }
+            step(&level, &mut surface, &mut ship);
+            assert!(ship.is_settling(), "{:?}", ship.contact);
+        }
+        assert_eq!(ship.legs_down, [true; 2], "never rocked onto both legs");
+
+        // Both legs down only counts once they've stayed down long enough
+        for _ in 1..SETTLE_TIME {
+            step(&level, &mut surface, &mut ship);
+            assert!(ship.is_settling(), "{:?}", ship.contact);
         }
+        step(&level, &mut surface, &mut ship);
+        assert!(ship.is_landed(), "{:?}", ship.contact);
+    }
 
-        p.draw(color);
+    #[test]
+    fn leaning_past_the_foot_tips_over() {
+        let (level, mut surface, mut ship) = touch_down_leaning(43.0);
+        assert!(ship.is_settling(), "{:?}", ship.contact);
+
+        for _ in 0..1000 {
+            if !ship.is_settling() {
+                break;
+            }
+            step(&level, &mut surface, &mut ship);
+        }
+        assert_eq!(
+            ship.contact,
+            Contact::Crashed {
+                kind: CrashKind::Tipped
+            }
+        );
     }
 }
@@ -1,120 +1,530 @@
+use crate::camera::Camera;
+use crate::contact::{BadLanding, Contact, CrashKind};
+use crate::fixed::{Fixed, FixedPoint};
 use crate::gfx;
+use crate::level::{Level, Pad, MAX_PADS};
+use crate::noise::Noise;
+use crate::platform::Platform;
+use crate::polygon::{Point, Polygon};
 use crate::rand_tab;
 use crate::ship::Ship;
-use crate::wasm4;
-use noilib_simple::NoiseGenerator;
+
+/// Where the ship's hull meets the ground
+#[derive(Copy, Clone, Debug)]
+pub struct Hit {
+    pub point: FixedPoint,
+    /// Unit normal of the ground, pointing up & out of it
+    pub normal: FixedPoint,
+    /// How far below the ground the hull reached
+    pub depth: Fixed,
+    pub pad: Option<Pad>,
+}
 
 pub struct Surface {
-    noise: NoiseGenerator,
-    pad_locations: [f32; PAD_COUNT],
-    heights: [i16; SCREEN_SZ as usize],
-    pub scale: f32,
+    pad_locations: [f32; MAX_PADS],
+    // Start, end & height of each pad in noise units, the pads are flat
+    pad_spans: [(Fixed, Fixed, Fixed); MAX_PADS],
+    // The ground across the world & its margins, from 0 at the lowest to just under 1
+    // at the highest in 16 bit fractions. The second level has half the samples, for
+    // zoomed out views. On the heap, as it's too big for the stack
+    heightmap: [Vec<u16>; MAP_LEVELS],
+    high: Fixed,
+    smooth: Fixed,
+    heights: [Fixed; SCREEN_SZ as usize],
+    // Which pad is under each column, if any
+    pad_cols: [Option<u8>; SCREEN_SZ as usize],
+    level: Level,
+    // View the ground was last worked out for
+    camera: Camera,
+    // Whole world squashed into the screen's width, height & whether it's a pad
+    minimap: [(u8, bool); SCREEN_SZ as usize],
 }
 
-const PAD_COUNT: usize = 4;
-const PAD_SZ: f32 = 0.5;
 const SCREEN_SZ: i32 = 160;
 const SCREEN_SZ_H: i32 = 80;
-const Y: f32 = 183.8;
-const HIGH: f32 = 160.0;
-const SMOOTH: f32 = 40.0;
+const Y: Fixed = Fixed::from_f64(183.8);
+// Width of the world in noise units, the pads are all placed inside it
+const WORLD_WIDTH: f32 = 20.0;
+const MINIMAP_HEIGHT: i32 = 10;
+// Heightmap samples per noise unit at its finest, & how far past the world's edges it
+// goes, enough for the view zoomed right out at an edge
+const MAP_DETAIL: i32 = 16;
+const MAP_MARGIN: i32 = 16;
+// Zoomed right out on the roughest level a column of the view is under a quarter of a
+// noise unit, where a third level would start, so two cover every view
+const MAP_LEVELS: usize = 2;
 
 impl Surface {
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: u32, level: &Level) -> Self {
         rand_tab::seed(seed as usize);
+        let noise = Noise::new(seed as u64);
         let mut surface = Surface {
-            noise: NoiseGenerator::new(seed as u64),
-            pad_locations: [0.0; PAD_COUNT],
-            scale: 1.0,
-            heights: [0; SCREEN_SZ as usize],
+            pad_locations: [0.0; MAX_PADS],
+            pad_spans: [(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO); MAX_PADS],
+            heightmap: [Vec::new(), Vec::new()],
+            high: Fixed::from_f32(level.high),
+            smooth: Fixed::from_f32(level.smooth),
+            heights: [Fixed::ZERO; SCREEN_SZ as usize],
+            pad_cols: [None; SCREEN_SZ as usize],
+            level: *level,
+            camera: Camera::new(),
+            minimap: [(0, false); SCREEN_SZ as usize],
         };
 
-        // randomize pad locations
-        for i in 0..PAD_COUNT {
-            surface.pad_locations[i] = 0.0 + (rand_tab::f64() * 18.0) as f32;
+        // place the pads, randomizing any the level leaves open
+        for (i, pad) in level.pads().iter().enumerate() {
+            surface.pad_locations[i] = match pad.x {
+                Some(x) => x,
+                None => 0.0 + (rand_tab::f64() * 18.0) as f32,
+            };
+        }
+
+        // The noise is only ever sampled here, so it's the same for drawing & collisions
+        let height = |x: Fixed| {
+            let h = (noise.perlin(x, Y) + Fixed::ONE) / Fixed::from_int(2);
+            h.clamp(Fixed::ZERO, Fixed::from_bits(u16::MAX as i32))
+        };
+
+        // Pads are flat at the height of their right hand end
+        for (i, pad) in level.pads().iter().enumerate() {
+            let end = Fixed::from_f32(surface.pad_locations[i] + pad.width);
+            surface.pad_spans[i] = (Fixed::from_f32(surface.pad_locations[i]), end, height(end));
+        }
+
+        // Finest level straight from the noise, then each coarser one blurs the one before
+        let samples = (WORLD_WIDTH as i32 + MAP_MARGIN * 2) * MAP_DETAIL;
+        surface.heightmap[0] = (0..=samples)
+            .map(|i| {
+                let x = Fixed::from_int(i - MAP_MARGIN * MAP_DETAIL) / Fixed::from_int(MAP_DETAIL);
+                height(x).to_bits() as u16
+            })
+            .collect();
+        for level in 1..MAP_LEVELS {
+            let finer = &surface.heightmap[level - 1];
+            let at = |i: usize| finer[i.min(finer.len() - 1)] as u32;
+            surface.heightmap[level] = (0..=finer.len() / 2)
+                .map(|i| {
+                    let left = at((i * 2).max(1) - 1);
+                    ((left + at(i * 2) * 2 + at(i * 2 + 1)) / 4) as u16
+                })
+                .collect();
+        }
+
+        let step = Fixed::from_f32(WORLD_WIDTH / SCREEN_SZ as f32);
+        for x in 0..SCREEN_SZ as usize {
+            let (h, pad_idx) = surface.ground(Fixed::from_int(x as i32) * step, step);
+            let h = (h * Fixed::from_int(MINIMAP_HEIGHT)).floor();
+            surface.minimap[x] = (h as u8, pad_idx.is_some());
         }
 
         surface
     }
 
-    pub fn draw(&mut self, x_offset: f32, y_offset: f32) {
-        let zoom = SMOOTH * self.scale;
-        let zoom_magic = 110.0;
+    // Ground at a point in noise units, from 0 at the lowest to 1 at the highest, plus
+    // the pad there if any. Step is the distance between the points being looked at,
+    // which picks the heightmap level with about that much detail
+    fn ground(&self, x: Fixed, step: Fixed) -> (Fixed, Option<usize>) {
+        let mut level = 0;
+        while level + 1 < MAP_LEVELS
+            && (step * Fixed::from_int(MAP_DETAIL)).floor() >> (level + 1) > 0
+        {
+            level += 1;
+        }
+
+        // Straight line between the two samples either side
+        let map = &self.heightmap[level];
+        let at = (x + Fixed::from_int(MAP_MARGIN)) * Fixed::from_int(MAP_DETAIL >> level);
+        let i = at.floor().clamp(0, map.len() as i32 - 2);
+        let a = Fixed::from_bits(map[i as usize] as i32);
+        let b = Fixed::from_bits(map[i as usize + 1] as i32);
+        let mut h = a + (b - a) * (at - Fixed::from_int(i)).clamp(Fixed::ZERO, Fixed::ONE);
 
+        let mut pad_idx = None;
+        for (i, &(start, end, pad_h)) in self.pad_spans[..self.level.pad_count].iter().enumerate() {
+            if x > start && x < end {
+                h = pad_h;
+                pad_idx = Some(i);
+            }
+        }
+
+        (h, pad_idx)
+    }
+
+    // Height of the ground at a screen column with the view centred on the offsets, plus
+    // the pad there if any. Looked up from the heightmap, so drawing & collisions agree
+    fn column(&self, x: i32, x_offset: Fixed, y_offset: Fixed) -> (Fixed, Option<usize>) {
+        let scale = self.camera.zoom;
+        let zoom = self.smooth * scale;
+        let zoom_magic = Fixed::from_int(110);
+
+        let x_zoom = (x_offset / self.smooth) + (Fixed::from_int(x - SCREEN_SZ_H) / zoom);
+        let (ground, pad_idx) = self.ground(x_zoom, Fixed::ONE / zoom);
+        let mut h = ground * self.high * scale;
+        h = h + y_offset - (zoom_magic * scale);
+
+        (h, pad_idx)
+    }
+
+    /// Work out the ground for the camera's view, this is the physics side of the
+    /// terrain, collision checks & drawing both use the result
+    pub fn update(&mut self, camera: &Camera) {
+        self.camera = *camera;
+        let view = camera.view();
         for x in 0..SCREEN_SZ {
-            let x_zoom = (x_offset / SMOOTH) + ((x as f32 - SCREEN_SZ_H as f32) / zoom);
-            let mut h = ((self.noise.perlin(x_zoom, Y) + 1.0) / 2.0) * HIGH * self.scale;
-            h = h + y_offset - (zoom_magic * self.scale);
-
-            // check if we're on a pad
-            let mut is_pad = false;
-            for i in 0..PAD_COUNT {
-                if (x_zoom > self.pad_locations[i]) && (x_zoom < self.pad_locations[i] + PAD_SZ) {
-                    let mut pad_h = ((self.noise.perlin(self.pad_locations[i] + PAD_SZ, Y) + 1.0)
-                        / 2.0)
-                        * HIGH
-                        * self.scale;
-                    pad_h = pad_h + y_offset - (zoom_magic * self.scale);
-                    h = pad_h;
-                    is_pad = true;
-                }
+            let (h, pad_idx) = self.column(x, view.x, view.y);
+            self.heights[x as usize] = h;
+            self.pad_cols[x as usize] = pad_idx.map(|i| i as u8);
+        }
+    }
+
+    /// Left & right edges of the world, as ship positions
+    pub fn bounds(&self) -> (Fixed, Fixed) {
+        (
+            Fixed::ZERO,
+            Fixed::from_f32(WORLD_WIDTH * self.level.smooth),
+        )
+    }
+
+    /// Ship position over the middle of each pad
+    pub fn pad_centres(&self) -> impl Iterator<Item = f64> + '_ {
+        self.level
+            .pads()
+            .iter()
+            .enumerate()
+            .map(|(i, pad)| ((self.pad_locations[i] + pad.width / 2.0) * self.level.smooth) as f64)
+    }
+
+    /// Ship position over the middle of the pad nearest to x
+    pub fn nearest_pad(&self, x: f64) -> Option<f64> {
+        self.pad_centres()
+            .min_by(|a, b| (a - x).abs().total_cmp(&(b - x).abs()))
+    }
+
+    /// Height of the ground above the bottom of the screen at a column, as of the last update
+    pub fn height_at(&self, x: i32) -> Fixed {
+        self.heights[x.clamp(0, SCREEN_SZ - 1) as usize]
+    }
+
+    pub fn draw<P: Platform>(&self, p: &mut P) {
+        // First & last column of each pad on screen, plus its height
+        let mut pad_spans: [Option<(i32, i32, i32)>; MAX_PADS] = [None; MAX_PADS];
+
+        for x in 0..SCREEN_SZ {
+            let h = self.heights[x as usize];
+            let pad_idx = self.pad_cols[x as usize];
+            if let Some(i) = pad_idx {
+                let span = pad_spans[i as usize].get_or_insert((x, x, h.floor()));
+                span.1 = x;
             }
 
-            self.heights[x as usize] = h as i16;
+            p.set_draw_color(2);
+            p.line(x, SCREEN_SZ, x, SCREEN_SZ - h.floor());
 
-            gfx::set_draw_color(2);
-            wasm4::line(x, SCREEN_SZ, x, SCREEN_SZ - h as i32);
+            if pad_idx.is_some() {
+                p.set_draw_color(4);
+                p.rect(x, SCREEN_SZ - h.floor(), 1, 1);
+                p.rect(x, SCREEN_SZ + 2 - h.floor(), 1, 1);
 
-            if is_pad {
-                gfx::set_draw_color(4);
-                wasm4::rect(x, SCREEN_SZ - h as i32, 1, 1);
-                wasm4::rect(x, SCREEN_SZ + 2 - h as i32, 1, 1);
+                p.set_draw_color(2);
+                p.rect(x, 18 + (x % 3), 1, 1);
+            }
+        }
 
-                gfx::set_draw_color(2);
-                wasm4::rect(x, 18 + (x % 3), 1, 1);
+        // Dotted line up from the ground at the edges of the world
+        let (left, right) = self.bounds();
+        for edge in [left, right] {
+            let x = SCREEN_SZ_H + ((edge - self.camera.view().x) * self.camera.zoom).floor();
+            if (0..SCREEN_SZ).contains(&x) {
+                p.set_draw_color(3);
+                let top = SCREEN_SZ - self.height_at(x).floor();
+                for y in (20..top).step_by(4) {
+                    p.rect(x, y, 1, 2);
+                }
+            }
+        }
+
+        // Label each pad with its score multiplier
+        for (i, span) in pad_spans.iter().enumerate() {
+            if let Some((first, last, h)) = *span {
+                let label = String::from("x") + self.level.pads[i].multiplier.to_string().as_str();
+                let x = (first + last) / 2 - label.len() as i32 * 4;
+                gfx::shadow_text(p, label.as_str(), x, SCREEN_SZ - h - 12, 0x4, 0x2);
+            }
+        }
+    }
 
-                // Use negative heights to indicate a pad
-                self.heights[x as usize] = -h as i16;
+    /// Arrows at the sides of the screen pointing to any pads out of view, with how far
+    /// away they are
+    pub fn draw_pad_pointers<P: Platform>(&self, p: &mut P) {
+        let ship_x = self.camera.follow.x.to_f64();
+        for (i, centre) in self.pad_centres().enumerate() {
+            let x = self.camera.to_screen(Point::new(centre, 0.0)).x;
+            if (0.0..SCREEN_SZ as f64).contains(&x) {
+                continue;
+            }
+
+            let y = 60 + i as i32 * 10;
+            let distance = gfx::whole((centre - ship_x).abs() / 10.0);
+            p.set_draw_color(4);
+            if x < 0.0 {
+                p.line(0, y + 3, 4, y);
+                p.line(0, y + 3, 4, y + 6);
+                gfx::shadow_text(p, distance.as_str(), 7, y, 0x4, 0x2);
+            } else {
+                p.line(SCREEN_SZ - 1, y + 3, SCREEN_SZ - 5, y);
+                p.line(SCREEN_SZ - 1, y + 3, SCREEN_SZ - 5, y + 6);
+                let text_x = SCREEN_SZ - 7 - distance.len() as i32 * 8;
+                gfx::shadow_text(p, distance.as_str(), text_x, y, 0x4, 0x2);
             }
         }
     }
 
-    pub fn check_collision(&self, x: f64, y: f64, ship: &Ship) -> u8 {
-        // 0 is no collision
-        // 1 is landed OK
-        // 2 is a crash
-        // 3+ is bad landing
+    /// The whole world in a strip across the screen, with the pads & where the ship is
+    pub fn draw_minimap<P: Platform>(&self, p: &mut P, top: i32) {
+        let bottom = top + MINIMAP_HEIGHT;
+        p.set_draw_color(1);
+        p.rect(0, top, SCREEN_SZ as u32, MINIMAP_HEIGHT as u32 + 1);
 
-        let mut h = self.heights[x as usize];
-        let mut is_pad = false;
-        if h < 0 {
-            is_pad = true;
-            h = -h;
+        for (x, &(h, pad)) in self.minimap.iter().enumerate() {
+            p.set_draw_color(if pad { 4 } else { 2 });
+            p.vline(x as i32, bottom - h as i32, h as u32 + 1);
         }
 
-        if y > SCREEN_SZ as f64 - h as f64 {
-            // Check if we're on a pad
-            if is_pad {
-                let ang = ship.angle.to_degrees() + 90.0;
-                let speed = ship.get_speed() * 100.0;
+        let (_, right) = self.bounds();
+        let ship_x = (self.camera.follow.x.to_f64() / right.to_f64() * SCREEN_SZ as f64) as i32;
+        p.set_draw_color(4);
+        p.rect(ship_x, top, 1, 3);
+    }
+
+    /// Deepest point where the edge a-b dips below the ground, if it does
+    pub fn segment_hit(&self, a: FixedPoint, b: FixedPoint) -> Option<Hit> {
+        self.segment_hit_by(a, b, |col| (self.heights[col], self.pad_cols[col]))
+    }
 
-                // Criteria for a safe & good landing
-                if speed > 10.0 {
-                    return 3;
+    /// Deepest point where the polygon cuts into the ground as it would be with the view
+    /// centred on the offsets, used to check the path between frames
+    pub fn hit_at(&self, poly: &Polygon, x_offset: Fixed, y_offset: Fixed) -> Option<Hit> {
+        // Work out the ground under the polygon once
+        let min_x = poly.points.iter().map(|pt| pt.x).min().unwrap_or_default();
+        let max_x = poly.points.iter().map(|pt| pt.x).max().unwrap_or_default();
+        let first = (min_x.floor() - 1).clamp(0, SCREEN_SZ - 1);
+        let last = (max_x.floor() + 1).clamp(0, SCREEN_SZ - 1);
+        let mut ground = Vec::new();
+        for col in first..=last {
+            let (h, pad_idx) = self.column(col, x_offset, y_offset);
+            ground.push((h, pad_idx.map(|i| i as u8)));
+        }
+        let column = |col: usize| ground[(col as i32).clamp(first, last) as usize - first as usize];
+
+        let mut deepest: Option<Hit> = None;
+        for i in 0..poly.points.len() {
+            let p1 = poly.points[i];
+            let p2 = poly.points[(i + 1) % poly.points.len()];
+            if let Some(hit) = self.segment_hit_by(p1, p2, column) {
+                if deepest.is_none_or(|d| hit.depth > d.depth) {
+                    deepest = Some(hit);
                 }
-                if ang.abs() > 1.1 {
-                    return 4;
+            }
+        }
+
+        deepest
+    }
+
+    // Edge test against any ground, column gives the height & pad at a screen column
+    fn segment_hit_by<F>(&self, a: FixedPoint, b: FixedPoint, column: F) -> Option<Hit>
+    where
+        F: Fn(usize) -> (Fixed, Option<u8>),
+    {
+        let (a, b) = if a.x <= b.x { (a, b) } else { (b, a) };
+        let mut deepest: Option<Hit> = None;
+        let ground =
+            |c: i32| Fixed::from_int(SCREEN_SZ) - column(c.clamp(0, SCREEN_SZ - 1) as usize).0;
+
+        // Each column is flat ground, so the lowest point of the edge over a column
+        // is at one end of the part of the edge above it
+        let first = a.x.floor().max(0);
+        let last = b.x.floor().min(SCREEN_SZ - 1);
+        for col in first..=last {
+            let x1 = a.x.max(Fixed::from_int(col));
+            let x2 = b.x.min(Fixed::from_int(col + 1));
+            for x in [x1, x2] {
+                let y = if b.x > a.x {
+                    a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x)
+                } else {
+                    a.y.max(b.y)
+                };
+                let depth = y - ground(col);
+                if depth > Fixed::ZERO && deepest.is_none_or(|d| depth > d.depth) {
+                    // Upward facing normal, from the slope across the neighbouring columns.
+                    // Pads are flat, even at an edge where the next column is terrain
+                    let pad = column(col as usize).1;
+                    let slope = match pad {
+                        Some(_) => Fixed::ZERO,
+                        None => (ground(col + 1) - ground(col - 1)) / Fixed::from_int(2),
+                    };
+                    let len = (slope * slope + Fixed::ONE).sqrt();
+
+                    deepest = Some(Hit {
+                        point: FixedPoint::new(x, ground(col)),
+                        normal: FixedPoint::new(slope / len, -Fixed::ONE / len),
+                        depth,
+                        pad: pad.map(|i| self.level.pads[i as usize]),
+                    });
                 }
-                if ship.get_velocity().x > 0.02 {
-                    return 5;
+            }
+        }
+
+        deepest
+    }
+
+    /// Judge a touch of the ground, safe landings need a pad and the ship within the
+    /// level's limits
+    pub fn check_collision(&self, hit: &Hit, ship: &Ship) -> Contact {
+        let Some(pad) = hit.pad else {
+            return Contact::Crashed {
+                kind: CrashKind::Terrain,
+            };
+        };
+
+        let ang = ship.angle.to_f64().to_degrees() + 90.0;
+        let speed = ship.get_speed() * 100.0;
+        let vx = ship.get_velocity().x;
+
+        // Criteria for a safe & good landing
+        if speed > self.level.max_speed {
+            return Contact::BadLanding(BadLanding::TooFast { speed });
+        }
+        if ang.abs() > self.level.max_angle {
+            return Contact::BadLanding(BadLanding::TooSteep { angle: ang });
+        }
+        if vx.abs() > self.level.max_drift {
+            return Contact::BadLanding(BadLanding::Sideways { vx });
+        }
+
+        // Up to 50 each for touching down slow and upright
+        let speed_bonus = (1.0 - speed / self.level.max_speed).max(0.0) * 50.0;
+        let angle_bonus = (1.0 - ang.abs() / self.level.max_angle).max(0.0) * 50.0;
+
+        Contact::Landed {
+            pad,
+            score_quality: (speed_bonus + angle_bonus).round() as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The first level's ground, with the ship upright over the middle of its first pad
+    fn over_pad(velocity: Point) -> (Surface, Ship) {
+        let mut level = Level::new(1);
+        level.start_velocity = velocity;
+        let surface = Surface::new(1234, &level);
+        let mut ship = Ship::for_level(&level);
+        ship.pos.x = Fixed::from_f64(surface.pad_centres().next().unwrap());
+        ship.angle = -Fixed::FRAC_PI_2;
+        ship.camera.reset(ship.pos);
+        (surface, ship)
+    }
+
+    // Judge a touch of the first pad, with the ship leaning by degrees off vertical
+    fn judge(velocity: Point, lean: f64) -> Contact {
+        let (surface, mut ship) = over_pad(velocity);
+        ship.angle += Fixed::from_f64(lean.to_radians());
+        let hit = Hit {
+            point: ship.pos,
+            normal: FixedPoint::new(Fixed::ZERO, -Fixed::ONE),
+            depth: Fixed::ONE,
+            pad: Some(surface.level.pads[0]),
+        };
+        surface.check_collision(&hit, &ship)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn too_fast() {
+        match judge(Point::new(0.0, 0.2), 0.0) {
+            Contact::BadLanding(BadLanding::TooFast { speed }) => assert!(close(speed, 20.0)),
+            contact => panic!("{:?}", contact),
+        }
+    }
+
+    #[test]
+    fn too_steep_either_way() {
+        for lean in [5.0, -5.0] {
+            match judge(Point::new(0.0, 0.05), lean) {
+                Contact::BadLanding(BadLanding::TooSteep { angle }) => {
+                    assert!(close(angle, lean), "{} for {}", angle, lean)
+                }
+                contact => panic!("{:?}", contact),
+            }
+        }
+    }
+
+    #[test]
+    fn drifting_either_way() {
+        for drift in [0.03, -0.03] {
+            match judge(Point::new(drift, 0.01), 0.0) {
+                Contact::BadLanding(BadLanding::Sideways { vx }) => {
+                    assert!(close(vx, drift), "{} for {}", vx, drift)
                 }
+                contact => panic!("{:?}", contact),
+            }
+        }
+    }
 
-                return 1;
+    #[test]
+    fn landing_quality() {
+        // Half the speed & angle limits score half of each bonus
+        let level = Level::new(1);
+        let cases = [
+            (0.0, 0.0, 100),
+            (level.max_speed / 200.0, 0.0, 75),
+            (0.0, level.max_angle / 2.0, 75),
+            (level.max_speed / 200.0, -level.max_angle / 2.0, 50),
+        ];
+        for (vy, lean, want) in cases {
+            match judge(Point::new(0.0, vy), lean) {
+                Contact::Landed { score_quality, .. } => {
+                    assert_eq!(score_quality, want, "{} at {}", vy, lean)
+                }
+                contact => panic!("{:?}", contact),
             }
+        }
+    }
+
+    #[test]
+    fn update_then_collide_lands_without_a_platform() {
+        let (mut surface, mut ship) = over_pad(Point::new(0.0, 0.0));
 
-            return 2;
+        // Lower the ship a pixel at a time, working out the ground for each view
+        while ship.contact == Contact::None {
+            assert!(
+                ship.pos.y < Fixed::from_int(1000),
+                "never touched the ground"
+            );
+            ship.pos.y += Fixed::ONE;
+            ship.camera.hold(ship.pos);
+            surface.update(&ship.camera);
+            ship.collide(&surface);
         }
 
-        return 0;
+        let pad = surface.level.pads[0];
+        assert_eq!(
+            ship.contact,
+            Contact::Settling {
+                pad,
+                score_quality: 100
+            }
+        );
+        let hit = ship.hit.unwrap();
+        assert_eq!(hit.pad, Some(pad));
+        assert_eq!(hit.normal, FixedPoint::new(Fixed::ZERO, -Fixed::ONE));
+        let ground = Fixed::from_int(SCREEN_SZ) - surface.height_at(hit.point.x.floor());
+        assert_eq!(hit.point.y, ground);
     }
 }
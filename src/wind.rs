@@ -0,0 +1,58 @@
+// Wind blowing across the level, a steady breeze plus gusts that come & go, blowing
+// harder higher up. It only pushes the ship through the air's drag, so it's worked
+// out in fixed point like the rest of the physics
+
+use crate::fixed::Fixed;
+use crate::level::Level;
+
+// Frames before the gusts repeat, each gust wave fits a whole number of times
+const GUST_CYCLE: u32 = 3600;
+const SLOW_GUSTS: i32 = 5;
+const FAST_GUSTS: i32 = 17;
+// Height of the ship where the wind dies down to its weakest, & that weakest share
+const CALM_Y: Fixed = Fixed::from_int(110);
+const CALM: Fixed = Fixed::from_f64(0.25);
+
+pub struct Wind {
+    base: Fixed,
+    gust: Fixed,
+    frame: u32,
+}
+
+impl Wind {
+    pub fn new(base: f64, gust: f64) -> Self {
+        Self {
+            base: Fixed::from_f64(base),
+            gust: Fixed::from_f64(gust),
+            frame: 0,
+        }
+    }
+
+    /// The level's wind, which is still on worlds with no air to carry it
+    pub fn for_level(level: &Level) -> Self {
+        if level.body.drag() == 0.0 {
+            return Wind::new(0.0, 0.0);
+        }
+        Wind::new(level.wind, level.gust)
+    }
+
+    pub fn update(&mut self) {
+        self.frame = (self.frame + 1) % GUST_CYCLE;
+    }
+
+    pub fn is_calm(&self) -> bool {
+        self.base == Fixed::ZERO && self.gust == Fixed::ZERO
+    }
+
+    /// Speed of the air at a height, positive blows to the right
+    pub fn at(&self, y: Fixed) -> Fixed {
+        // Two waves of gusts, a slow swell with quicker flurries on top
+        let cycle = Fixed::from_int(self.frame as i32) / Fixed::from_int(GUST_CYCLE as i32);
+        let turn = cycle * Fixed::PI * Fixed::from_int(2);
+        let gusts = (turn * Fixed::from_int(SLOW_GUSTS)).sin() * Fixed::from_f64(0.6)
+            + (turn * Fixed::from_int(FAST_GUSTS)).sin() * Fixed::from_f64(0.4);
+
+        let height = ((CALM_Y - y) / CALM_Y).clamp(CALM, Fixed::ONE);
+        (self.base + self.gust * gusts) * height
+    }
+}
@@ -0,0 +1,66 @@
+// Drives the whole game through the headless backend, checking what it puts on screen
+// & plays, the way a player would see it
+
+use cart::game::Game;
+use cart::headless::{Call, Headless};
+use cart::level::Level;
+use cart::platform::{BUTTON_1, TONE_NOISE};
+use cart::scene::Scene;
+
+// Run a frame with the given buttons held, keeping only that frame's calls
+fn frame(game: &mut Game, p: &mut Headless, gamepad: u8) {
+    p.clear();
+    p.gamepad = gamepad;
+    game.update(p);
+}
+
+// Let go of the buttons then press X, so the game sees a fresh press
+fn press_x(game: &mut Game, p: &mut Headless) {
+    frame(game, p, 0);
+    frame(game, p, BUTTON_1);
+}
+
+#[test]
+fn title_to_playing_to_crash() {
+    let mut game = Game::new();
+    let mut p = Headless::new();
+
+    frame(&mut game, &mut p, 0);
+    assert_eq!(game.get_scene(), Scene::Title);
+    assert!(p.texts().any(|t| t.contains("WASM LANDER")));
+
+    press_x(&mut game, &mut p);
+    assert_eq!(game.get_scene(), Scene::LevelIntro);
+    assert!(p.texts().any(|t| t == "LEVEL 1"));
+
+    press_x(&mut game, &mut p);
+    assert_eq!(game.get_scene(), Scene::Playing);
+    assert!(p.texts().any(|t| t.starts_with("F: ")));
+
+    // Hands off the controls, the ship falls until it hits the ground
+    let mut frames = 0;
+    while game.get_scene() == Scene::Playing {
+        frame(&mut game, &mut p, 0);
+        frames += 1;
+        assert!(frames < 5000, "never came down");
+    }
+    assert_eq!(game.get_scene(), Scene::Crashed);
+    assert_eq!(game.get_lives(), 2);
+    assert!(p
+        .tones()
+        .any(|t| matches!(t, Call::Tone(_, _, _, flags) if *flags == TONE_NOISE)));
+
+    frame(&mut game, &mut p, 0);
+    assert!(p.texts().any(|t| t == "Lives left: 2"));
+}
+
+#[test]
+fn fixed_levels_keep_their_terrain() {
+    let mut game = Game::new();
+    for level in 1..=5 {
+        game.play_level(1234, level);
+        let want = game.get_level().seed.unwrap_or(1234);
+        assert_eq!(game.get_replay().seed, want, "level {}", level);
+    }
+    assert!((1..=5).any(|level| Level::new(level).seed.is_some()));
+}
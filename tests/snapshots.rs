@@ -0,0 +1,46 @@
+// Renders each shot in assets/snapshots/shots.txt with the simulator & checks it matches
+// the golden image byte for byte, `make snapshots-update` re-renders them all
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn shots_match_the_golden_images() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let snapshots = root.join("assets/snapshots");
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    std::fs::create_dir_all(&out).unwrap();
+
+    let shots = std::fs::read_to_string(snapshots.join("shots.txt")).unwrap();
+    let mut changed = Vec::new();
+    for line in shots
+        .lines()
+        .filter(|l| !l.starts_with('#') && !l.trim().is_empty())
+    {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap();
+        let png = out.join(format!("{}.png", name));
+
+        // Input scripts are named from the top of the repo, like the Makefile runs it
+        let status = Command::new(env!("CARGO_BIN_EXE_lander-sim"))
+            .current_dir(root)
+            .args(words)
+            .arg("--png")
+            .arg(&png)
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "{}: lander-sim failed", name);
+
+        let golden = std::fs::read(snapshots.join(format!("{}.png", name))).unwrap();
+        if std::fs::read(&png).unwrap() != golden {
+            changed.push(name);
+        }
+    }
+    assert!(
+        changed.is_empty(),
+        "{:?} changed, see {}",
+        changed,
+        out.display()
+    );
+}